@@ -1,20 +1,19 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, Criterion};
 use eta_algorithms::data_structs::array::Array;
+use std::hint::black_box;
 
 fn benchmark_array(initial_size: usize, data_size: usize) {
     // Benchmark custom Array
     let mut custom_array = Array::new(initial_size);
-    let mut custom_len = 0;
     let mut current_size = initial_size;
 
     // Push
-    for i in 0..data_size {
+    for (custom_len, i) in (0..data_size).enumerate() {
         if custom_len == current_size {
             current_size *= 2;
             custom_array.resize(current_size);
         }
         custom_array[custom_len] = i;
-        custom_len += 1;
     }
 
     // Sum (simulating pop)
@@ -1,6 +1,7 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, Criterion};
 use eta_algorithms::data_structs::queue::Queue;
 use std::collections::VecDeque;
+use std::hint::black_box;
 
 fn custom_queue_benchmark(num_elements: usize) {
     // Benchmark custom Queue
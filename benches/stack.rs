@@ -1,5 +1,6 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, Criterion};
 use eta_algorithms::data_structs::stack::Stack;
+use std::hint::black_box;
 
 fn stack_benchmark(data_size: usize) {
     let mut stack = Stack::<i32>::new(2);
@@ -0,0 +1,36 @@
+use std::io::{Read, Result, Write};
+use std::mem::size_of;
+use std::ptr;
+
+use crate::data_structs::array::Array;
+use crate::data_structs::binary::{
+    as_bytes, check_element_size, invalid_data, payload_len, read_header, read_payload, to_usize, write_header, write_padding, ByteOrder,
+    Pod,
+};
+
+pub(super) const TAG: &[u8; 4] = b"ETAR";
+
+/// Header with the element size and capacity, then the elements as raw native-endian bytes.
+/// Reading on a target with the other byte order fails with `InvalidData`. The payload is aligned,
+/// `ArrayView::from_written` views it in place.
+impl<T> Array<T>
+where
+    T: Pod,
+{
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        write_header(writer, TAG, ByteOrder::Native, &[size_of::<T>() as u64, self.capacity as u64])?;
+        let bytes = as_bytes(self.as_slice());
+        writer.write_all(bytes)?;
+        write_padding(writer, bytes.len())
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> Result<Self> {
+        let [element_size, capacity] = read_header(reader, TAG, ByteOrder::Native)?;
+        check_element_size::<T>(element_size)?;
+        let capacity = to_usize(capacity)?;
+        let bytes = read_payload(reader, payload_len::<T>(capacity)?)?;
+        let mut array = Self::try_new(capacity).ok_or_else(|| invalid_data("Capacity too large"))?;
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), array.as_mut_ptr() as *mut u8, bytes.len()) };
+        Ok(array)
+    }
+}
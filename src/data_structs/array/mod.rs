@@ -1,28 +1,35 @@
-use std::alloc::{realloc, Layout};
+use std::alloc::Layout;
 use std::cmp::min;
 use std::marker::PhantomData;
 use std::ops::{Index, IndexMut};
 use std::ptr;
 use std::ptr::{addr_of_mut, copy_nonoverlapping};
 
+use crate::data_structs::raw_alloc::{Global, RawAlloc};
+
+mod binary;
 pub mod iterator;
+pub mod view;
 
-pub struct Array<T>
+pub struct Array<T, A = Global>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     phantom_data: PhantomData<()>, // For compile time borrow checking correctness
     layout: Layout,
     data: *mut T,
     capacity: usize,
+    alloc: A,
 }
 
-impl<T> Clone for Array<T>
+impl<T, A> Clone for Array<T, A>
 where
     T: Copy + Sized,
+    A: RawAlloc + Clone,
 {
     fn clone(&self) -> Self {
-        let array = Self::new(self.capacity);
+        let array = Self::new_in(self.capacity, self.alloc.clone());
         unsafe { copy_nonoverlapping(self.data, array.data, self.capacity) }
         array
     }
@@ -32,13 +39,97 @@ impl<T> Array<T>
 where
     T: Copy + Sized,
 {
+    pub fn new(capacity: usize) -> Self {
+        Self::new_in(capacity, Global)
+    }
+    #[inline(always)]
+    pub fn try_new(capacity: usize) -> Option<Self> {
+        Self::try_new_in(capacity, Global)
+    }
+
+    #[inline(always)]
+    pub fn new_default_bytes(capacity: usize, default: u8) -> Self {
+        let arr = Self::new(capacity);
+        unsafe { ptr::write_bytes(arr.data, default, capacity) };
+        arr
+    }
+
+    #[inline(always)]
+    pub fn new_with_default(capacity: usize, default: T) -> Self
+    where
+        T: Copy,
+    {
+        let mut arr = Self::new(capacity);
+        arr.fill(default);
+        arr
+    }
+
+    /// Copies the contents of the vector into the array.
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        let arr = Self::new(vec.len());
+        unsafe {
+            copy_nonoverlapping(vec.as_ptr(), arr.data, arr.capacity);
+            arr
+        }
+    }
+
+    /// Copies the contents of the slice into the array.
+    pub fn from_slice(slice: &[T]) -> Self {
+        let arr = Self::new(slice.len());
+        unsafe {
+            copy_nonoverlapping(slice.as_ptr(), arr.data, arr.capacity);
+            arr
+        }
+    }
+}
+
+impl<T, A> Array<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    pub fn new_in(capacity: usize, alloc: A) -> Self {
+        if Layout::array::<T>(capacity).is_err() {
+            panic!("Failed to create layout");
+        }
+        Self::try_new_in(capacity, alloc).expect("Failed to allocate memory")
+    }
+    /// Same as `new_in` but returns None instead of panicking when the layout overflows or the allocation fails
+    pub fn try_new_in(capacity: usize, alloc: A) -> Option<Self> {
+        let layout = Layout::array::<T>(capacity).ok()?;
+        let data = unsafe { alloc.alloc(layout) as *mut T };
+        if data.is_null() {
+            return None;
+        }
+        Some(Array {
+            phantom_data: PhantomData,
+            layout,
+            data,
+            capacity,
+            alloc,
+        })
+    }
+
+    /// Copies the contents of the slice into an array backed by `alloc`.
+    pub fn from_slice_in(slice: &[T], alloc: A) -> Self {
+        let arr = Self::new_in(slice.len(), alloc);
+        unsafe {
+            copy_nonoverlapping(slice.as_ptr(), arr.data, arr.capacity);
+            arr
+        }
+    }
+
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
     #[inline(always)]
     pub fn capacity(&self) -> usize {
         self.capacity
     }
     pub fn resize(&mut self, new_capacity: usize) {
         let new_layout = Layout::array::<T>(new_capacity).expect("Failed to create layout");
-        let new_ptr = unsafe { realloc(self.data as *mut u8, new_layout, new_layout.size()) };
+        let new_ptr = unsafe { self.alloc.realloc(self.data as *mut u8, self.layout, new_layout.size()) };
         if new_ptr.is_null() {
             panic!("Failed to allocate memory");
         }
@@ -59,36 +150,6 @@ where
         self.data
     }
 
-    pub fn new(capacity: usize) -> Self {
-        let layout = Layout::array::<T>(capacity).expect("Failed to create layout");
-        let data = unsafe { std::alloc::alloc(layout) as *mut T };
-        if data.is_null() {
-            panic!("Failed to allocate memory");
-        }
-        Array {
-            phantom_data: PhantomData,
-            layout,
-            data,
-            capacity,
-        }
-    }
-
-    #[inline(always)]
-    pub fn new_default_bytes(capacity: usize, default: u8) -> Self {
-        let arr = Self::new(capacity);
-        unsafe { ptr::write_bytes(arr.data, default, capacity) };
-        arr
-    }
-    #[inline(always)]
-    pub fn new_with_default(capacity: usize, default: T) -> Self
-    where
-        T: Copy,
-    {
-        let mut arr = Self::new(capacity);
-        arr.fill(default);
-        arr
-    }
-
     pub fn fill(&mut self, value: T)
     where
         T: Copy,
@@ -98,7 +159,7 @@ where
         }
     }
     #[inline(always)]
-    pub fn iter(&self) -> iterator::ArrayIterator<T> {
+    pub fn iter(&self) -> iterator::ArrayIterator<'_, T> {
         iterator::ArrayIterator {
             phantom_data: &self.phantom_data,
             data: self.data,
@@ -107,7 +168,7 @@ where
     }
 
     #[inline(always)]
-    pub fn iter_mut(&mut self) -> iterator::ArrayIteratorMut<T> {
+    pub fn iter_mut(&mut self) -> iterator::ArrayIteratorMut<'_, T> {
         iterator::ArrayIteratorMut {
             phantom_data: &mut self.phantom_data,
             data: self.data,
@@ -116,7 +177,7 @@ where
     }
 
     #[inline(always)]
-    pub fn iter_range(&self, start: usize, end: usize) -> iterator::ArrayIterator<T> {
+    pub fn iter_range(&self, start: usize, end: usize) -> iterator::ArrayIterator<'_, T> {
         iterator::ArrayIterator {
             phantom_data: &self.phantom_data,
             data: unsafe { self.data.add(start) },
@@ -125,7 +186,7 @@ where
     }
 
     #[inline(always)]
-    pub fn iter_range_mut(&mut self, start: usize, end: usize) -> iterator::ArrayIteratorMut<T> {
+    pub fn iter_range_mut(&mut self, start: usize, end: usize) -> iterator::ArrayIteratorMut<'_, T> {
         iterator::ArrayIteratorMut {
             phantom_data: &mut self.phantom_data,
             data: unsafe { self.data.add(start) },
@@ -135,6 +196,10 @@ where
 
     /// Extremely unsafe as it bypasses lifetime checks. Use if you know what you are doing.
     /// This is good for cases where you need dynamically retrieve multiple mutable iterators to chunks of non-overlapping data.
+    ///
+    /// # Safety
+    /// The iterator is not tied to the array, the array has to outlive it and nothing else may access
+    /// `start..end` while it is alive.
     #[inline(always)]
     pub unsafe fn iter_range_mut_unchecked(&mut self, start: usize, end: usize) -> iterator::ArrayIteratorMut<'static, T> {
         static mut PHANTOM: PhantomData<()> = PhantomData;
@@ -145,23 +210,6 @@ where
         }
     }
 
-    /// Copies the contents of the vector into the array.
-    pub fn from_vec(vec: Vec<T>) -> Self {
-        let arr = Self::new(vec.len());
-        unsafe {
-            copy_nonoverlapping(vec.as_ptr(), arr.data, arr.capacity);
-            arr
-        }
-    }
-    /// Copies the contents of the slice into the array.
-    pub fn from_slice(slice: &[T]) -> Self {
-        let arr = Self::new(slice.len());
-        unsafe {
-            copy_nonoverlapping(slice.as_ptr(), arr.data, arr.capacity);
-            arr
-        }
-    }
-
     pub fn as_slice(&self) -> &[T] {
         unsafe { std::slice::from_raw_parts(self.data, self.capacity) }
     }
@@ -235,31 +283,37 @@ where
         arr.push(unsafe { std::slice::from_raw_parts_mut(ptr, chunk_size + remainder) });
         arr
     }
+    /// # Safety
+    /// `index` must be less than `capacity()`.
     #[inline(always)]
     pub unsafe fn index_unchecked(&self, index: usize) -> &T {
         self.data.add(index).as_ref().unwrap()
     }
 
+    /// # Safety
+    /// `index` must be less than `capacity()`.
     #[inline(always)]
     pub unsafe fn index_unchecked_mut(&mut self, index: usize) -> &mut T {
         self.data.add(index).as_mut().unwrap()
     }
 }
 
-impl<T> Drop for Array<T>
+impl<T, A> Drop for Array<T, A>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     fn drop(&mut self) {
         unsafe {
-            std::alloc::dealloc(self.data as *mut u8, self.layout);
+            self.alloc.dealloc(self.data as *mut u8, self.layout);
         }
     }
 }
 
-impl<T> Index<usize> for Array<T>
+impl<T, A> Index<usize> for Array<T, A>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     type Output = T;
 
@@ -273,9 +327,10 @@ where
     }
 }
 
-impl<T> IndexMut<usize> for Array<T>
+impl<T, A> IndexMut<usize> for Array<T, A>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     #[inline(always)]
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
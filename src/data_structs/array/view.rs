@@ -0,0 +1,166 @@
+use std::mem::size_of;
+use std::ops::{Index, IndexMut, Range};
+use std::slice;
+
+use crate::data_structs::array::binary::TAG;
+use crate::data_structs::binary::{cast_slice, cast_slice_mut, parse_header, Pod, ViewError};
+
+/// Bytes of the elements in a buffer written by `Array::write_to`
+fn written_range<T: Pod>(bytes: &[u8]) -> Result<Range<usize>, ViewError> {
+    let ([element_size, capacity], start) = parse_header(bytes, TAG)?;
+    if element_size != size_of::<T>() as u64 {
+        return Err(ViewError::InvalidHeader);
+    }
+    let len = usize::try_from(capacity)
+        .ok()
+        .and_then(|capacity| capacity.checked_mul(size_of::<T>()))
+        .ok_or(ViewError::InvalidHeader)?;
+    match start.checked_add(len) {
+        Some(end) if end <= bytes.len() => Ok(start..end),
+        _ => Err(ViewError::InvalidLength),
+    }
+}
+
+/// Read-only array over a borrowed byte buffer, for example a memory mapped file. Nothing is copied,
+/// the buffer has to be aligned for `T` and a multiple of its size.
+#[derive(Clone, Copy)]
+pub struct ArrayView<'a, T>
+where
+    T: Pod,
+{
+    data: &'a [T],
+}
+
+impl<'a, T> ArrayView<'a, T>
+where
+    T: Pod,
+{
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ViewError> {
+        Ok(ArrayView { data: cast_slice(bytes)? })
+    }
+    /// Views the elements of a buffer written by `Array::write_to`, for example a memory mapped file.
+    /// The buffer has to start at an alignment of 16 and come from a target with the same byte order.
+    pub fn from_written(bytes: &'a [u8]) -> Result<Self, ViewError> {
+        let range = written_range::<T>(bytes)?;
+        Self::from_bytes(&bytes[range])
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        self.data.get(index)
+    }
+    #[inline(always)]
+    pub fn as_slice(&self) -> &'a [T] {
+        self.data
+    }
+    #[inline(always)]
+    pub fn iter(&self) -> slice::Iter<'a, T> {
+        self.data.iter()
+    }
+}
+
+impl<T> Index<usize> for ArrayView<'_, T>
+where
+    T: Pod,
+{
+    type Output = T;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.data.len() {
+            panic!("Index out of bounds");
+        }
+        unsafe { self.data.get_unchecked(index) }
+    }
+}
+
+/// Mutable array over a borrowed byte buffer, writes go straight to the buffer
+pub struct ArrayViewMut<'a, T>
+where
+    T: Pod,
+{
+    data: &'a mut [T],
+}
+
+impl<'a, T> ArrayViewMut<'a, T>
+where
+    T: Pod,
+{
+    pub fn from_bytes(bytes: &'a mut [u8]) -> Result<Self, ViewError> {
+        Ok(ArrayViewMut {
+            data: cast_slice_mut(bytes)?,
+        })
+    }
+    /// Same as `ArrayView::from_written`, writes go straight to the written elements
+    pub fn from_written(bytes: &'a mut [u8]) -> Result<Self, ViewError> {
+        let range = written_range::<T>(bytes)?;
+        Self::from_bytes(&mut bytes[range])
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+    #[inline(always)]
+    pub fn as_view(&self) -> ArrayView<'_, T> {
+        ArrayView { data: self.data }
+    }
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.data.get(index)
+    }
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.data.get_mut(index)
+    }
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        self.data
+    }
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data
+    }
+    #[inline(always)]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+    #[inline(always)]
+    pub fn fill(&mut self, value: T) {
+        self.data.fill(value);
+    }
+}
+
+impl<T> Index<usize> for ArrayViewMut<'_, T>
+where
+    T: Pod,
+{
+    type Output = T;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.data.len() {
+            panic!("Index out of bounds");
+        }
+        unsafe { self.data.get_unchecked(index) }
+    }
+}
+
+impl<T> IndexMut<usize> for ArrayViewMut<'_, T>
+where
+    T: Pod,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        if index >= self.data.len() {
+            panic!("Index out of bounds");
+        }
+        unsafe { self.data.get_unchecked_mut(index) }
+    }
+}
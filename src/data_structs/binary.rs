@@ -0,0 +1,207 @@
+use std::fmt::{Display, Formatter};
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::mem::{align_of, size_of, size_of_val};
+use std::slice;
+
+/// Plain old data that can be written and read back as raw bytes.
+///
+/// # Safety
+/// Implementors must have no padding and no pointers, and every bit pattern must be a valid value.
+pub unsafe trait Pod: Copy + Sized + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(unsafe impl Pod for $t {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+#[inline(always)]
+pub fn as_bytes<T: Pod>(values: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(values.as_ptr() as *const u8, size_of_val(values)) }
+}
+#[inline(always)]
+pub fn as_bytes_mut<T: Pod>(values: &mut [T]) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut u8, size_of_val(values)) }
+}
+
+/// Why a byte buffer cannot be viewed as a slice of `T`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewError {
+    /// The buffer does not start at a multiple of the alignment of `T`
+    Misaligned,
+    /// The buffer is too short or not a multiple of the size of `T`
+    InvalidLength,
+    /// The buffer does not start with the header `write_to` writes for this type
+    InvalidHeader,
+    /// The payload was written on a target with the other byte order
+    ByteOrderMismatch,
+}
+
+impl Display for ViewError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViewError::Misaligned => write!(f, "Buffer is not aligned for the element type"),
+            ViewError::InvalidLength => write!(f, "Buffer length does not fit the element type"),
+            ViewError::InvalidHeader => write!(f, "Buffer does not start with a valid header"),
+            ViewError::ByteOrderMismatch => write!(f, "Buffer was written with the other byte order"),
+        }
+    }
+}
+
+impl std::error::Error for ViewError {}
+
+#[inline(always)]
+fn check_view<T: Pod>(ptr: *const u8, len: usize) -> std::result::Result<usize, ViewError> {
+    if size_of::<T>() == 0 || len % size_of::<T>() != 0 {
+        return Err(ViewError::InvalidLength);
+    }
+    if (ptr as usize) % align_of::<T>() != 0 {
+        return Err(ViewError::Misaligned);
+    }
+    Ok(len / size_of::<T>())
+}
+
+/// Reinterprets the bytes as elements without copying
+pub fn cast_slice<T: Pod>(bytes: &[u8]) -> std::result::Result<&[T], ViewError> {
+    let len = check_view::<T>(bytes.as_ptr(), bytes.len())?;
+    Ok(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const T, len) })
+}
+pub fn cast_slice_mut<T: Pod>(bytes: &mut [u8]) -> std::result::Result<&mut [T], ViewError> {
+    let len = check_view::<T>(bytes.as_ptr(), bytes.len())?;
+    Ok(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, len) })
+}
+
+/// Written after the tag in the byte order of the payload, so a reader on a target with the other
+/// byte order rejects the stream instead of misreading the elements
+const BYTE_ORDER_MARK: u32 = 0x0102_0304;
+
+/// Headers and payloads are zero padded to a multiple of this many bytes. A stream that starts at
+/// this alignment, a memory map for example, keeps every payload aligned for any `Pod` type.
+const ALIGN: usize = 16;
+
+/// Payloads are read in chunks of this many bytes, a corrupt header cannot make the reader
+/// allocate much more than the stream actually holds
+const READ_CHUNK: usize = 1 << 16;
+
+/// Byte order of the payload that follows a header
+#[derive(Clone, Copy)]
+pub(crate) enum ByteOrder {
+    Native,
+    Little,
+}
+
+impl ByteOrder {
+    #[inline(always)]
+    fn mark(self) -> [u8; 4] {
+        match self {
+            ByteOrder::Native => BYTE_ORDER_MARK.to_ne_bytes(),
+            ByteOrder::Little => BYTE_ORDER_MARK.to_le_bytes(),
+        }
+    }
+}
+
+#[inline(always)]
+fn padding(len: usize) -> usize {
+    len.next_multiple_of(ALIGN) - len
+}
+
+#[inline(always)]
+fn header_len(fields: usize) -> usize {
+    (8 + fields * 8).next_multiple_of(ALIGN)
+}
+
+/// The header is the 4 byte tag, the byte order mark of the payload and little-endian u64 fields
+pub(crate) fn write_header(writer: &mut impl Write, tag: &[u8; 4], order: ByteOrder, fields: &[u64]) -> Result<()> {
+    writer.write_all(tag)?;
+    writer.write_all(&order.mark())?;
+    for field in fields {
+        writer.write_all(&field.to_le_bytes())?;
+    }
+    writer.write_all(&[0; ALIGN][..header_len(fields.len()) - 8 - fields.len() * 8])
+}
+
+/// Pads a payload of `len` bytes
+pub(crate) fn write_padding(writer: &mut impl Write, len: usize) -> Result<()> {
+    writer.write_all(&[0; ALIGN][..padding(len)])
+}
+
+pub(crate) fn read_header<const N: usize>(reader: &mut impl Read, tag: &[u8; 4], order: ByteOrder) -> Result<[u64; N]> {
+    let mut read_tag = [0u8; 4];
+    reader.read_exact(&mut read_tag)?;
+    if &read_tag != tag {
+        return Err(invalid_data("Unexpected tag"));
+    }
+    let mut mark = [0u8; 4];
+    reader.read_exact(&mut mark)?;
+    if mark != order.mark() {
+        return Err(invalid_data("Byte order mismatch"));
+    }
+    let mut fields = [0u64; N];
+    for field in fields.iter_mut() {
+        let mut bytes = [0u8; 8];
+        reader.read_exact(&mut bytes)?;
+        *field = u64::from_le_bytes(bytes);
+    }
+    reader.read_exact(&mut [0; ALIGN][..header_len(N) - 8 - N * 8])?;
+    Ok(fields)
+}
+
+/// Parses a header written by `write_header` at the start of `bytes`. Returns the fields and the
+/// offset of the payload, which has to be in native byte order to be viewed.
+pub(crate) fn parse_header<const N: usize>(bytes: &[u8], tag: &[u8; 4]) -> std::result::Result<([u64; N], usize), ViewError> {
+    let len = header_len(N);
+    if bytes.len() < len || &bytes[..4] != tag {
+        return Err(ViewError::InvalidHeader);
+    }
+    if bytes[4..8] != ByteOrder::Native.mark() {
+        return Err(ViewError::ByteOrderMismatch);
+    }
+    let mut fields = [0u64; N];
+    for (field, chunk) in fields.iter_mut().zip(bytes[8..].chunks_exact(8)) {
+        *field = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    Ok((fields, len))
+}
+
+/// Reads the `len` byte payload and its padding. The buffer grows as the bytes arrive, a stream
+/// that ends early is invalid data since the header promised more.
+pub(crate) fn read_payload(reader: &mut impl Read, len: usize) -> Result<Vec<u8>> {
+    let short = |error: Error| match error.kind() {
+        ErrorKind::UnexpectedEof => invalid_data("Payload is shorter than the header says"),
+        _ => error,
+    };
+    let mut bytes = Vec::new();
+    while bytes.len() < len {
+        let start = bytes.len();
+        bytes.resize(start + (len - start).min(READ_CHUNK), 0);
+        reader.read_exact(&mut bytes[start..]).map_err(short)?;
+    }
+    reader.read_exact(&mut [0; ALIGN][..padding(len)]).map_err(short)?;
+    Ok(bytes)
+}
+
+/// Byte length of `len` elements of `T`
+pub(crate) fn payload_len<T>(len: usize) -> Result<usize> {
+    len.checked_mul(size_of::<T>()).ok_or_else(|| invalid_data("Length too large"))
+}
+
+/// Checks that the stored element size matches `T`
+pub(crate) fn check_element_size<T>(element_size: u64) -> Result<()> {
+    if element_size != size_of::<T>() as u64 {
+        return Err(invalid_data("Element size mismatch"));
+    }
+    Ok(())
+}
+
+pub(crate) fn to_usize(field: u64) -> Result<usize> {
+    usize::try_from(field).map_err(|_| invalid_data("Length does not fit into usize"))
+}
+
+#[inline(always)]
+pub(crate) fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
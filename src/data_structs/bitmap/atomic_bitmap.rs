@@ -1,8 +1,9 @@
 use std::alloc::Layout;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, DIV_SHIFT};
+use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, BIT_MASK, DIV_SHIFT};
 use crate::data_structs::bitmap::handle::Handle;
+use crate::data_structs::bitmap::Bitmap;
 
 #[derive(Clone, Copy)]
 pub enum Mode {
@@ -10,6 +11,31 @@ pub enum Mode {
     Strict,
 }
 
+impl Mode {
+    #[inline(always)]
+    pub fn load_ordering(self) -> Ordering {
+        match self {
+            Mode::Relaxed => Ordering::Relaxed,
+            Mode::Strict => Ordering::Acquire,
+        }
+    }
+    #[inline(always)]
+    pub fn store_ordering(self) -> Ordering {
+        match self {
+            Mode::Relaxed => Ordering::Relaxed,
+            Mode::Strict => Ordering::Release,
+        }
+    }
+    /// Ordering for read-modify-write operations
+    #[inline(always)]
+    pub fn rmw_ordering(self) -> Ordering {
+        match self {
+            Mode::Relaxed => Ordering::Relaxed,
+            Mode::Strict => Ordering::AcqRel,
+        }
+    }
+}
+
 pub struct AtomicBitmap {
     data: *mut AtomicUsize,
     bit_capacity: usize,
@@ -47,27 +73,55 @@ impl AtomicBitmap {
     pub fn to_indices_false(&self, mode: Mode) -> Vec<usize> {
         let mut indices = Vec::new();
         for i in 0..self.bit_capacity {
-            if unsafe { self.get_unchecked(i, mode) == false } {
+            if unsafe { !self.get_unchecked(i, mode) } {
                 indices.push(i);
             }
         }
         indices
     }
 
+    /// Whether every bit of every handle is set, each word is loaded separately
     pub fn check_batch(&self, handles: &[Handle], mode: Mode) -> bool {
         for handle in handles {
-            let val = unsafe {
-                match mode {
-                    Mode::Relaxed => (*self.data.add(handle.chunk as usize)).load(Ordering::Relaxed),
-                    Mode::Strict => (*self.data.add(handle.chunk as usize)).load(Ordering::Acquire),
-                }
-            };
+            self.check_handle(handle);
+            let val = unsafe { (*self.data.add(handle.chunk)).load(mode.load_ordering()) };
             if (val & handle.bit_mask) != handle.bit_mask {
                 return false;
             }
         }
         true
     }
+    /// Whether any bit of any handle is set
+    pub fn check_batch_any(&self, handles: &[Handle], mode: Mode) -> bool {
+        for handle in handles {
+            self.check_handle(handle);
+            if unsafe { (*self.data.add(handle.chunk)).load(mode.load_ordering()) } & handle.bit_mask != 0 {
+                return true;
+            }
+        }
+        false
+    }
+    /// One `fetch_or` per handle, the batch as a whole is not atomic
+    pub fn set_batch(&self, handles: &[Handle], mode: Mode) {
+        for handle in handles {
+            self.check_handle(handle);
+            unsafe { (*self.data.add(handle.chunk)).fetch_or(handle.bit_mask, mode.rmw_ordering()) };
+        }
+    }
+    /// One `fetch_and` per handle, the batch as a whole is not atomic
+    pub fn clear_batch(&self, handles: &[Handle], mode: Mode) {
+        for handle in handles {
+            self.check_handle(handle);
+            unsafe { (*self.data.add(handle.chunk)).fetch_and(!handle.bit_mask, mode.rmw_ordering()) };
+        }
+    }
+    /// One `fetch_xor` per handle, the batch as a whole is not atomic
+    pub fn toggle_batch(&self, handles: &[Handle], mode: Mode) {
+        for handle in handles {
+            self.check_handle(handle);
+            unsafe { (*self.data.add(handle.chunk)).fetch_xor(handle.bit_mask, mode.rmw_ordering()) };
+        }
+    }
 
     #[inline(always)]
     pub fn bit_capacity(&self) -> usize {
@@ -82,22 +136,7 @@ impl AtomicBitmap {
         if bit_index >= self.bit_capacity {
             panic!("Bit index out of bounds");
         }
-
-        let offset = bit_index >> DIV_SHIFT;
-        let bit_offset = bit_index & (BIT_END_OFFSET);
-        unsafe {
-            let ptr = self.data.add(offset);
-            match mode {
-                Mode::Relaxed => (*ptr).store(
-                    ((*ptr).load(Ordering::Relaxed) & !(1 << bit_offset)) | ((value as usize) << bit_offset),
-                    Ordering::Relaxed,
-                ),
-                Mode::Strict => (*ptr).store(
-                    ((*ptr).load(Ordering::Acquire) & !(1 << bit_offset)) | ((value as usize) << bit_offset),
-                    Ordering::Release,
-                ),
-            }
-        }
+        unsafe { self.set_unchecked(bit_index, value, mode) }
     }
     #[inline(always)]
     pub fn get(&self, bit_index: usize, mode: Mode) -> Option<bool> {
@@ -109,40 +148,214 @@ impl AtomicBitmap {
         let bit_offset = bit_index & (BIT_END_OFFSET);
         unsafe {
             let ptr = self.data.add(offset);
-            match mode {
-                Mode::Relaxed => Some(((*ptr).load(Ordering::Relaxed) & (1 << bit_offset)) != 0),
-                Mode::Strict => Some(((*ptr).load(Ordering::Acquire) & (1 << bit_offset)) != 0),
-            }
+            Some(((*ptr).load(mode.load_ordering()) & (1 << bit_offset)) != 0)
         }
     }
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
     #[inline(always)]
     pub unsafe fn set_unchecked(&self, bit_index: usize, value: bool, mode: Mode) {
-        let offset = bit_index >> DIV_SHIFT;
-        let bit_offset = bit_index & (BIT_END_OFFSET);
-        unsafe {
-            let ptr = self.data.add(offset);
-            match mode {
-                Mode::Relaxed => (*ptr).store(
-                    ((*ptr).load(Ordering::Relaxed) & !(1 << bit_offset)) | ((value as usize) << bit_offset),
-                    Ordering::Relaxed,
-                ),
-                Mode::Strict => (*ptr).store(
-                    ((*ptr).load(Ordering::Acquire) & !(1 << bit_offset)) | ((value as usize) << bit_offset),
-                    Ordering::Release,
-                ),
-            }
+        if value {
+            self.fetch_set_unchecked(bit_index, mode);
+        } else {
+            self.fetch_clear_unchecked(bit_index, mode);
         }
     }
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
     #[inline(always)]
     pub unsafe fn get_unchecked(&self, bit_index: usize, mode: Mode) -> bool {
         let offset = bit_index >> DIV_SHIFT;
         let bit_offset = bit_index & (BIT_END_OFFSET);
         unsafe {
             let ptr = self.data.add(offset);
-            match mode {
-                Mode::Relaxed => (*ptr).load(Ordering::Relaxed) & (1 << bit_offset) != 0,
-                Mode::Strict => (*ptr).load(Ordering::Acquire) & (1 << bit_offset) != 0,
+            (*ptr).load(mode.load_ordering()) & (1 << bit_offset) != 0
+        }
+    }
+
+    /// Sets the bit with a single `fetch_or` and returns its previous value
+    #[inline(always)]
+    pub fn fetch_set(&self, bit_index: usize, mode: Mode) -> bool {
+        self.check_bounds(bit_index);
+        unsafe { self.fetch_set_unchecked(bit_index, mode) }
+    }
+    /// Clears the bit with a single `fetch_and` and returns its previous value
+    #[inline(always)]
+    pub fn fetch_clear(&self, bit_index: usize, mode: Mode) -> bool {
+        self.check_bounds(bit_index);
+        unsafe { self.fetch_clear_unchecked(bit_index, mode) }
+    }
+    /// Flips the bit with a single `fetch_xor` and returns its previous value
+    #[inline(always)]
+    pub fn fetch_toggle(&self, bit_index: usize, mode: Mode) -> bool {
+        self.check_bounds(bit_index);
+        unsafe { self.fetch_toggle_unchecked(bit_index, mode) }
+    }
+    /// Sets the bit and returns its previous value, like `atomic_flag::test_and_set`.
+    /// False means this call is the one that set it, which is how a slot gets claimed.
+    #[inline(always)]
+    pub fn test_and_set(&self, bit_index: usize, mode: Mode) -> bool {
+        self.fetch_set(bit_index, mode)
+    }
+    /// Writes `value` only if the bit currently equals `expected`. Retries `compare_exchange_weak`
+    /// while other bits of the word change underneath, returns whether the write happened.
+    pub fn set_if(&self, bit_index: usize, expected: bool, value: bool, mode: Mode) -> bool {
+        self.check_bounds(bit_index);
+        let mask = 1 << (bit_index & BIT_END_OFFSET);
+        let word = unsafe { &*self.data.add(bit_index >> DIV_SHIFT) };
+        let mut current = word.load(mode.load_ordering());
+        loop {
+            if (current & mask != 0) != expected {
+                return false;
+            }
+            let new = if value { current | mask } else { current & !mask };
+            match word.compare_exchange_weak(current, new, mode.rmw_ordering(), mode.load_ordering()) {
+                Ok(_) => return true,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
+    #[inline(always)]
+    pub unsafe fn fetch_set_unchecked(&self, bit_index: usize, mode: Mode) -> bool {
+        let mask = 1 << (bit_index & BIT_END_OFFSET);
+        (*self.data.add(bit_index >> DIV_SHIFT)).fetch_or(mask, mode.rmw_ordering()) & mask != 0
+    }
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
+    #[inline(always)]
+    pub unsafe fn fetch_clear_unchecked(&self, bit_index: usize, mode: Mode) -> bool {
+        let mask = 1 << (bit_index & BIT_END_OFFSET);
+        (*self.data.add(bit_index >> DIV_SHIFT)).fetch_and(!mask, mode.rmw_ordering()) & mask != 0
+    }
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
+    #[inline(always)]
+    pub unsafe fn fetch_toggle_unchecked(&self, bit_index: usize, mode: Mode) -> bool {
+        let mask = 1 << (bit_index & BIT_END_OFFSET);
+        (*self.data.add(bit_index >> DIV_SHIFT)).fetch_xor(mask, mode.rmw_ordering()) & mask != 0
+    }
+
+    /// Copies every word with the ordering of `mode`. Words are loaded one at a time, so the copy
+    /// is only consistent per word while other threads keep writing.
+    pub fn snapshot(&self, mode: Mode) -> Bitmap {
+        let mut bitmap = Bitmap::new(self.bit_capacity);
+        for i in 0..self.capacity {
+            unsafe { *bitmap.data.add(i) = (*self.data.add(i)).load(mode.load_ordering()) };
+        }
+        bitmap.clear_tail();
+        bitmap
+    }
+
+    /// Counts the set bits in `lower_bound..upper_bound`, loading one word at a time
+    #[inline(always)]
+    pub fn count_ones(&self, lower_bound: usize, upper_bound: usize, mode: Mode) -> usize {
+        self.check_range(lower_bound, upper_bound);
+        unsafe { self.count_ones_unchecked(lower_bound, upper_bound, mode) }
+    }
+    #[inline(always)]
+    pub fn count_zeros(&self, lower_bound: usize, upper_bound: usize, mode: Mode) -> usize {
+        self.check_range(lower_bound, upper_bound);
+        upper_bound - lower_bound - unsafe { self.count_ones_unchecked(lower_bound, upper_bound, mode) }
+    }
+
+    #[inline(always)]
+    pub fn first_one(&self, bit_index: usize, mode: Mode) -> Option<usize> {
+        self.check_bounds(bit_index);
+        unsafe { self.find_unchecked(bit_index, self.bit_capacity, false, mode) }
+    }
+    #[inline(always)]
+    pub fn first_zero(&self, bit_index: usize, mode: Mode) -> Option<usize> {
+        self.check_bounds(bit_index);
+        unsafe { self.find_unchecked(bit_index, self.bit_capacity, true, mode) }
+    }
+    #[inline(always)]
+    pub fn first_one_bounds(&self, lower_bound: usize, upper_bound: usize, mode: Mode) -> Option<usize> {
+        self.check_range(lower_bound, upper_bound);
+        unsafe { self.find_unchecked(lower_bound, upper_bound, false, mode) }
+    }
+    #[inline(always)]
+    pub fn first_zero_bounds(&self, lower_bound: usize, upper_bound: usize, mode: Mode) -> Option<usize> {
+        self.check_range(lower_bound, upper_bound);
+        unsafe { self.find_unchecked(lower_bound, upper_bound, true, mode) }
+    }
+
+    /// Counts the set bits in `lower_bound..upper_bound`
+    ///
+    /// # Safety
+    /// `upper_bound` must be at most `bit_capacity()`.
+    pub unsafe fn count_ones_unchecked(&self, lower_bound: usize, upper_bound: usize, mode: Mode) -> usize {
+        if lower_bound >= upper_bound {
+            return 0;
+        }
+        let lower_offset = lower_bound >> DIV_SHIFT;
+        let upper_offset = (upper_bound - 1) >> DIV_SHIFT;
+        let mut counter = 0;
+        for offset in lower_offset..=upper_offset {
+            let data = (*self.data.add(offset)).load(mode.load_ordering());
+            counter += (data & Self::range_mask(offset, lower_bound, upper_bound)).count_ones() as usize;
+        }
+        counter
+    }
+
+    /// First bit in `lower_bound..upper_bound` that is set, or unset if `zero` is true
+    unsafe fn find_unchecked(&self, lower_bound: usize, upper_bound: usize, zero: bool, mode: Mode) -> Option<usize> {
+        if lower_bound >= upper_bound {
+            return None;
+        }
+        let lower_offset = lower_bound >> DIV_SHIFT;
+        let upper_offset = (upper_bound - 1) >> DIV_SHIFT;
+        for offset in lower_offset..=upper_offset {
+            let mut data = (*self.data.add(offset)).load(mode.load_ordering());
+            if zero {
+                data = !data;
             }
+            data &= Self::range_mask(offset, lower_bound, upper_bound);
+            if data != 0 {
+                return Some((offset << DIV_SHIFT) + data.trailing_zeros() as usize);
+            }
+        }
+        None
+    }
+
+    /// Bits of the word at `offset` inside of `lower_bound..upper_bound`, the range has to touch the word
+    #[inline(always)]
+    pub(crate) fn range_mask(offset: usize, lower_bound: usize, upper_bound: usize) -> usize {
+        let first = offset << DIV_SHIFT;
+        let lower = lower_bound.max(first);
+        let upper = upper_bound.min(first + BIT_END_OFFSET + 1);
+        (BIT_MASK >> (BIT_END_OFFSET + 1 - (upper - lower))) << (lower & BIT_END_OFFSET)
+    }
+
+    #[inline(always)]
+    fn check_handle(&self, handle: &Handle) {
+        if !handle.fits(self.bit_capacity) {
+            panic!("Handle out of bounds");
+        }
+    }
+
+    #[inline(always)]
+    fn check_range(&self, lower_bound: usize, upper_bound: usize) {
+        if lower_bound > upper_bound {
+            panic!("Lower bound cannot be greater than upper bound");
+        }
+        if upper_bound > self.bit_capacity {
+            panic!("Upper bound out of bounds");
+        }
+    }
+
+    /// Word at `offset`, has to be below `capacity`
+    #[inline(always)]
+    pub(crate) unsafe fn word_unchecked(&self, offset: usize) -> &AtomicUsize {
+        &*self.data.add(offset)
+    }
+
+    #[inline(always)]
+    fn check_bounds(&self, bit_index: usize) {
+        if bit_index >= self.bit_capacity {
+            panic!("Bit index out of bounds");
         }
     }
 }
@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+use std::io::{Read, Result, Write};
+use std::mem::size_of;
+use std::slice;
+
+use crate::data_structs::binary::{invalid_data, read_header, read_payload, to_usize, write_header, write_padding, ByteOrder};
+use crate::data_structs::bitmap::Bitmap;
+
+pub(super) const TAG: &[u8; 4] = b"ETBM";
+
+/// Header with the bit capacity, then the bits as little-endian bytes, zero padded past the last used byte.
+/// The byte stream does not depend on the word size, bit `i` is bit `i % 8` of byte `i / 8`. On
+/// little-endian targets that is the word layout of a bitmap, so `BitmapView::from_written` can view it in place.
+impl Bitmap {
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        write_header(writer, TAG, ByteOrder::Little, &[self.bit_capacity as u64])?;
+        let bytes = self.le_bytes();
+        writer.write_all(&bytes)?;
+        write_padding(writer, bytes.len())
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> Result<Self> {
+        let [bit_capacity] = read_header(reader, TAG, ByteOrder::Little)?;
+        let bit_capacity = to_usize(bit_capacity)?;
+        let bytes = read_payload(reader, bit_capacity.div_ceil(8))?;
+        let mut bitmap = Bitmap::try_new(bit_capacity).ok_or_else(|| invalid_data("Capacity too large"))?;
+        unsafe { bitmap.raw_bytes_mut()[..bytes.len()].copy_from_slice(&bytes) };
+        bitmap.words_from_le();
+        Ok(bitmap)
+    }
+
+    /// The bits as a little-endian byte stream, borrowed on little-endian targets
+    pub(crate) fn le_bytes(&self) -> Cow<'_, [u8]> {
+        let byte_len = self.bit_capacity.div_ceil(8);
+        if cfg!(target_endian = "little") {
+            return Cow::Borrowed(unsafe { &self.raw_bytes()[..byte_len] });
+        }
+        let mut bytes = Vec::with_capacity(self.used_words() * size_of::<usize>());
+        for i in 0..self.used_words() {
+            bytes.extend_from_slice(&unsafe { *self.data.add(i) }.to_le_bytes());
+        }
+        bytes.truncate(byte_len);
+        Cow::Owned(bytes)
+    }
+
+    /// Inverse of `le_bytes`, None if the byte count does not match `bit_capacity`
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_le_bytes(bit_capacity: usize, bytes: &[u8]) -> Option<Bitmap> {
+        if bytes.len() != bit_capacity.div_ceil(8) {
+            return None;
+        }
+        let mut bitmap = Bitmap::new(bit_capacity);
+        unsafe { bitmap.raw_bytes_mut()[..bytes.len()].copy_from_slice(bytes) };
+        bitmap.words_from_le();
+        Some(bitmap)
+    }
+
+    #[inline(always)]
+    fn words_from_le(&mut self) {
+        if cfg!(target_endian = "big") {
+            for i in 0..self.used_words() {
+                unsafe { *self.data.add(i) = usize::from_le(*self.data.add(i)) };
+            }
+        }
+        // Stray bits past bit_capacity in the last byte would break the tail invariant
+        self.clear_tail();
+    }
+
+    #[inline(always)]
+    unsafe fn raw_bytes(&self) -> &[u8] {
+        slice::from_raw_parts(self.data as *const u8, self.used_words() * size_of::<usize>())
+    }
+    #[inline(always)]
+    unsafe fn raw_bytes_mut(&mut self) -> &mut [u8] {
+        slice::from_raw_parts_mut(self.data as *mut u8, self.used_words() * size_of::<usize>())
+    }
+}
@@ -0,0 +1,738 @@
+use std::cmp::Ordering;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+use std::slice;
+
+use crate::data_structs::bitmap::Bitmap;
+
+const CHUNK_SHIFT: usize = 16;
+const CHUNK_MASK: usize = (1 << CHUNK_SHIFT) - 1;
+const CHUNK_WORDS: usize = (1 << CHUNK_SHIFT) / 64;
+/// Above this cardinality a sorted array takes more space than a bitmap
+const ARRAY_MAX: usize = 4096;
+/// The compressed bitmap addresses a 2^32 universe
+const UNIVERSE: usize = 1 << 32;
+
+type Words = Box<[u64; CHUNK_WORDS]>;
+
+/// A container holds the low 16 bits of every set bit in its 2^16 chunk
+#[derive(Clone)]
+enum Container {
+    Array(Vec<u16>),
+    Bitmap {
+        words: Words,
+        len: usize,
+    },
+    /// Inclusive (start, end) runs, sorted and non adjacent
+    Run {
+        runs: Vec<(u16, u16)>,
+        len: usize,
+    },
+}
+
+impl Container {
+    fn len(&self) -> usize {
+        match self {
+            Container::Array(values) => values.len(),
+            Container::Bitmap { len, .. } | Container::Run { len, .. } => *len,
+        }
+    }
+
+    fn from_runs(runs: Vec<(u16, u16)>) -> Container {
+        let len = runs.iter().map(|(start, end)| (*end - *start) as usize + 1).sum();
+        Container::Run { runs, len }
+    }
+
+    fn contains(&self, low: u16) -> bool {
+        match self {
+            Container::Array(values) => values.binary_search(&low).is_ok(),
+            Container::Bitmap { words, .. } => words[low as usize >> 6] & (1 << (low & 63)) != 0,
+            Container::Run { runs, .. } => Self::find_run(runs, low).is_ok(),
+        }
+    }
+
+    /// Ok with the index of the run containing `low`, Err with the index of the first run after it
+    fn find_run(runs: &[(u16, u16)], low: u16) -> Result<usize, usize> {
+        let index = runs.partition_point(|(_, end)| *end < low);
+        if index < runs.len() && runs[index].0 <= low {
+            Ok(index)
+        } else {
+            Err(index)
+        }
+    }
+
+    fn to_words(&self) -> Words {
+        let mut words: Words = Box::new([0; CHUNK_WORDS]);
+        match self {
+            Container::Array(values) => {
+                for value in values {
+                    words[*value as usize >> 6] |= 1 << (value & 63);
+                }
+            }
+            Container::Bitmap { words: source, .. } => words.copy_from_slice(&source[..]),
+            Container::Run { runs, .. } => {
+                for (start, end) in runs {
+                    for value in *start as usize..=*end as usize {
+                        words[value >> 6] |= 1 << (value & 63);
+                    }
+                }
+            }
+        }
+        words
+    }
+
+    /// Picks the smaller of array and bitmap, None if nothing is set
+    fn from_words(words: Words) -> Option<Container> {
+        let len = words.iter().map(|word| word.count_ones() as usize).sum();
+        if len == 0 {
+            return None;
+        }
+        if len > ARRAY_MAX {
+            return Some(Container::Bitmap { words, len });
+        }
+        let mut values = Vec::with_capacity(len);
+        for (index, word) in words.iter().enumerate() {
+            let mut word = *word;
+            while word != 0 {
+                values.push(((index << 6) + word.trailing_zeros() as usize) as u16);
+                word &= word - 1;
+            }
+        }
+        Some(Container::Array(values))
+    }
+
+    /// Returns false if the bit was already set
+    fn insert(&mut self, low: u16) -> bool {
+        match self {
+            Container::Array(values) => {
+                let index = match values.binary_search(&low) {
+                    Ok(_) => return false,
+                    Err(index) => index,
+                };
+                values.insert(index, low);
+                if values.len() > ARRAY_MAX {
+                    let words = self.to_words();
+                    *self = Container::Bitmap { words, len: ARRAY_MAX + 1 };
+                }
+                true
+            }
+            Container::Bitmap { words, len } => {
+                let word = &mut words[low as usize >> 6];
+                let mask = 1 << (low & 63);
+                if *word & mask != 0 {
+                    return false;
+                }
+                *word |= mask;
+                *len += 1;
+                true
+            }
+            Container::Run { runs, len } => {
+                let index = match Self::find_run(runs, low) {
+                    Ok(_) => return false,
+                    Err(index) => index,
+                };
+                // Extend or merge the neighbouring runs, `low` is not in either of them
+                let joins_previous = index > 0 && runs[index - 1].1 as usize + 1 == low as usize;
+                let joins_next = index < runs.len() && runs[index].0 as usize == low as usize + 1;
+                match (joins_previous, joins_next) {
+                    (true, true) => {
+                        runs[index - 1].1 = runs[index].1;
+                        runs.remove(index);
+                    }
+                    (true, false) => runs[index - 1].1 = low,
+                    (false, true) => runs[index].0 = low,
+                    (false, false) => runs.insert(index, (low, low)),
+                }
+                *len += 1;
+                true
+            }
+        }
+    }
+
+    /// Returns false if the bit was not set
+    fn remove(&mut self, low: u16) -> bool {
+        match self {
+            Container::Array(values) => match values.binary_search(&low) {
+                Ok(index) => {
+                    values.remove(index);
+                    true
+                }
+                Err(_) => false,
+            },
+            Container::Bitmap { words, len } => {
+                let word = &mut words[low as usize >> 6];
+                let mask = 1 << (low & 63);
+                if *word & mask == 0 {
+                    return false;
+                }
+                *word &= !mask;
+                *len -= 1;
+                if *len <= ARRAY_MAX {
+                    let words = self.to_words();
+                    *self = Self::from_words(words).unwrap_or(Container::Array(Vec::new()));
+                }
+                true
+            }
+            Container::Run { runs, len } => {
+                let index = match Self::find_run(runs, low) {
+                    Ok(index) => index,
+                    Err(_) => return false,
+                };
+                let (start, end) = runs[index];
+                if start == end {
+                    runs.remove(index);
+                } else if low == start {
+                    runs[index].0 = low + 1;
+                } else if low == end {
+                    runs[index].1 = low - 1;
+                } else {
+                    runs[index].1 = low - 1;
+                    runs.insert(index + 1, (low + 1, end));
+                }
+                *len -= 1;
+                true
+            }
+        }
+    }
+
+    /// Number of set bits below `low`, `low` can be 2^16
+    fn rank(&self, low: usize) -> usize {
+        match self {
+            Container::Array(values) => values.partition_point(|value| (*value as usize) < low),
+            Container::Bitmap { words, .. } => {
+                let word = low >> 6;
+                let mut count: usize = words[..word].iter().map(|word| word.count_ones() as usize).sum();
+                if word < CHUNK_WORDS {
+                    count += (words[word] & ((1 << (low & 63)) - 1)).count_ones() as usize;
+                }
+                count
+            }
+            Container::Run { runs, .. } => runs
+                .iter()
+                .take_while(|(start, _)| (*start as usize) < low)
+                .map(|(start, end)| (*end as usize).min(low - 1) - *start as usize + 1)
+                .sum(),
+        }
+    }
+
+    /// First set bit at or above `low`
+    fn next_one(&self, low: usize) -> Option<u16> {
+        match self {
+            Container::Array(values) => {
+                let index = values.partition_point(|value| (*value as usize) < low);
+                values.get(index).copied()
+            }
+            Container::Bitmap { words, .. } => {
+                let mut index = low >> 6;
+                if index >= CHUNK_WORDS {
+                    return None;
+                }
+                let mut word = words[index] & (u64::MAX << (low & 63));
+                loop {
+                    if word != 0 {
+                        return Some(((index << 6) + word.trailing_zeros() as usize) as u16);
+                    }
+                    index += 1;
+                    if index == CHUNK_WORDS {
+                        return None;
+                    }
+                    word = words[index];
+                }
+            }
+            Container::Run { runs, .. } => {
+                let index = runs.partition_point(|(_, end)| (*end as usize) < low);
+                runs.get(index).map(|(start, _)| (*start as usize).max(low) as u16)
+            }
+        }
+    }
+
+    /// First unset bit at or above `low`, `low` is below 2^16
+    fn next_zero(&self, low: usize) -> Option<u16> {
+        match self {
+            Container::Array(values) => {
+                let mut index = values.partition_point(|value| (*value as usize) < low);
+                let mut candidate = low;
+                while index < values.len() && values[index] as usize == candidate {
+                    candidate += 1;
+                    index += 1;
+                }
+                (candidate <= CHUNK_MASK).then_some(candidate as u16)
+            }
+            Container::Bitmap { words, .. } => {
+                let mut index = low >> 6;
+                let mut word = !words[index] & (u64::MAX << (low & 63));
+                loop {
+                    if word != 0 {
+                        return Some(((index << 6) + word.trailing_zeros() as usize) as u16);
+                    }
+                    index += 1;
+                    if index == CHUNK_WORDS {
+                        return None;
+                    }
+                    word = !words[index];
+                }
+            }
+            Container::Run { runs, .. } => match Self::find_run(runs, low as u16) {
+                // Runs are not adjacent, so the bit after a run is unset
+                Ok(index) => runs[index].1.checked_add(1),
+                Err(_) => Some(low as u16),
+            },
+        }
+    }
+
+    fn last_one(&self) -> Option<u16> {
+        match self {
+            Container::Array(values) => values.last().copied(),
+            Container::Bitmap { words, .. } => {
+                let index = words.iter().rposition(|word| *word != 0)?;
+                Some(((index << 6) + 63 - words[index].leading_zeros() as usize) as u16)
+            }
+            Container::Run { runs, .. } => runs.last().map(|(_, end)| *end),
+        }
+    }
+
+    fn runs(&self) -> Vec<(u16, u16)> {
+        let mut runs: Vec<(u16, u16)> = Vec::new();
+        for value in self.iter() {
+            match runs.last_mut() {
+                Some((_, end)) if *end as usize + 1 == value as usize => *end = value,
+                _ => runs.push((value, value)),
+            }
+        }
+        runs
+    }
+
+    /// Switches to whichever representation takes the least space
+    fn optimize(self) -> Container {
+        let runs = match &self {
+            Container::Run { runs, .. } => runs.clone(),
+            _ => self.runs(),
+        };
+        let len = self.len();
+        let run_size = runs.len() * 4;
+        let other_size = if len > ARRAY_MAX { CHUNK_WORDS * 8 } else { len * 2 };
+        if run_size < other_size {
+            return Self::from_runs(runs);
+        }
+        match self {
+            Container::Run { .. } => Self::from_words(self.to_words()).unwrap(),
+            container => container,
+        }
+    }
+
+    fn iter(&self) -> ContainerIterator<'_> {
+        match self {
+            Container::Array(values) => ContainerIterator::Array(values.iter()),
+            Container::Bitmap { words, .. } => ContainerIterator::Bitmap {
+                words,
+                index: 0,
+                word: words[0],
+            },
+            Container::Run { runs, .. } => ContainerIterator::Run {
+                runs: runs.iter(),
+                next: 1,
+                end: 0,
+            },
+        }
+    }
+
+    fn combine(lhs: &Container, rhs: &Container, op: fn(u64, u64) -> u64) -> Option<Container> {
+        let lhs_words = lhs.to_words();
+        let mut words = rhs.to_words();
+        for (word, lhs_word) in words.iter_mut().zip(lhs_words.iter()) {
+            *word = op(*lhs_word, *word);
+        }
+        Self::from_words(words)
+    }
+
+    fn intersect(lhs: &Container, rhs: &Container) -> Option<Container> {
+        let (Container::Array(lhs_values), Container::Array(rhs_values)) = (lhs, rhs) else {
+            return Self::combine(lhs, rhs, |lhs, rhs| lhs & rhs);
+        };
+        let mut values = Vec::with_capacity(lhs_values.len().min(rhs_values.len()));
+        let (mut i, mut j) = (0, 0);
+        while i < lhs_values.len() && j < rhs_values.len() {
+            match lhs_values[i].cmp(&rhs_values[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    values.push(lhs_values[i]);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        if values.is_empty() {
+            return None;
+        }
+        Some(Container::Array(values))
+    }
+}
+
+enum ContainerIterator<'a> {
+    Array(slice::Iter<'a, u16>),
+    Bitmap {
+        words: &'a [u64; CHUNK_WORDS],
+        index: usize,
+        word: u64,
+    },
+    /// `next` and `end` are u32 so an exhausted run (next = end + 1) can't overflow
+    Run {
+        runs: slice::Iter<'a, (u16, u16)>,
+        next: u32,
+        end: u32,
+    },
+}
+
+impl Iterator for ContainerIterator<'_> {
+    type Item = u16;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            ContainerIterator::Array(values) => values.next().copied(),
+            ContainerIterator::Bitmap { words, index, word } => loop {
+                if *word != 0 {
+                    let value = (*index << 6) + word.trailing_zeros() as usize;
+                    *word &= *word - 1;
+                    return Some(value as u16);
+                }
+                // Stays on the last word once exhausted so further calls keep returning None
+                if *index + 1 >= CHUNK_WORDS {
+                    return None;
+                }
+                *index += 1;
+                *word = words[*index];
+            },
+            ContainerIterator::Run { runs, next, end } => {
+                if *next > *end {
+                    let (start, run_end) = runs.next()?;
+                    *next = *start as u32;
+                    *end = *run_end as u32;
+                }
+                let value = *next as u16;
+                *next += 1;
+                Some(value)
+            }
+        }
+    }
+}
+
+/// Roaring style bitmap over a 2^32 universe. Every 2^16 chunk that has a set bit owns a container
+/// that is either a sorted array, a dense bitmap or a list of runs, whichever is the smallest.
+#[derive(Clone, Default)]
+pub struct CompressedBitmap {
+    keys: Vec<u16>,
+    containers: Vec<Container>,
+}
+
+impl CompressedBitmap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    fn split(bit_index: usize) -> (u16, u16) {
+        if bit_index >= UNIVERSE {
+            panic!("Bit index out of bounds");
+        }
+        ((bit_index >> CHUNK_SHIFT) as u16, (bit_index & CHUNK_MASK) as u16)
+    }
+
+    #[inline(always)]
+    fn join(key: u16, low: u16) -> usize {
+        ((key as usize) << CHUNK_SHIFT) | low as usize
+    }
+
+    /// Number of set bits
+    pub fn len(&self) -> usize {
+        self.containers.iter().map(Container::len).sum()
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty()
+    }
+    /// Number of containers, one for every 2^16 chunk that has a set bit
+    #[inline(always)]
+    pub fn container_count(&self) -> usize {
+        self.containers.len()
+    }
+
+    pub fn set(&mut self, bit_index: usize, value: bool) {
+        let (key, low) = Self::split(bit_index);
+        match self.keys.binary_search(&key) {
+            Ok(index) => {
+                if value {
+                    self.containers[index].insert(low);
+                    return;
+                }
+                let container = &mut self.containers[index];
+                container.remove(low);
+                if container.len() == 0 {
+                    self.keys.remove(index);
+                    self.containers.remove(index);
+                }
+            }
+            Err(index) => {
+                if value {
+                    self.keys.insert(index, key);
+                    self.containers.insert(index, Container::Array(vec![low]));
+                }
+            }
+        }
+    }
+
+    /// None if `bit_index` is outside the 2^32 universe
+    #[inline(always)]
+    pub fn get(&self, bit_index: usize) -> Option<bool> {
+        if bit_index >= UNIVERSE {
+            return None;
+        }
+        let (key, low) = Self::split(bit_index);
+        match self.keys.binary_search(&key) {
+            Ok(index) => Some(self.containers[index].contains(low)),
+            Err(_) => Some(false),
+        }
+    }
+
+    /// Number of set bits in `lower_bound..upper_bound`
+    pub fn count_ones(&self, lower_bound: usize, upper_bound: usize) -> usize {
+        if lower_bound > upper_bound {
+            panic!("Lower bound cannot be greater than upper bound");
+        }
+        self.rank(upper_bound) - self.rank(lower_bound)
+    }
+
+    /// Number of unset bits in `lower_bound..upper_bound`, `upper_bound` is at most 2^32
+    pub fn count_zeros(&self, lower_bound: usize, upper_bound: usize) -> usize {
+        if upper_bound > UNIVERSE {
+            panic!("Upper bound out of bounds");
+        }
+        upper_bound - lower_bound - self.count_ones(lower_bound, upper_bound)
+    }
+
+    /// Number of set bits below `bit_index`
+    fn rank(&self, bit_index: usize) -> usize {
+        let bit_index = bit_index.min(UNIVERSE);
+        let key = bit_index >> CHUNK_SHIFT;
+        let (below, partial) = match self.keys.binary_search_by_key(&key, |container_key| *container_key as usize) {
+            Ok(index) => (index, self.containers[index].rank(bit_index & CHUNK_MASK)),
+            Err(index) => (index, 0),
+        };
+        self.containers[..below].iter().map(Container::len).sum::<usize>() + partial
+    }
+
+    /// First set bit at or above `bit_index`
+    pub fn first_one(&self, bit_index: usize) -> Option<usize> {
+        if bit_index >= UNIVERSE {
+            return None;
+        }
+        let (key, low) = Self::split(bit_index);
+        let start = self.keys.partition_point(|container_key| *container_key < key);
+        for index in start..self.keys.len() {
+            let from = if self.keys[index] == key { low as usize } else { 0 };
+            if let Some(found) = self.containers[index].next_one(from) {
+                return Some(Self::join(self.keys[index], found));
+            }
+        }
+        None
+    }
+
+    /// First unset bit at or above `bit_index`, chunks without a container are all unset
+    pub fn first_zero(&self, bit_index: usize) -> Option<usize> {
+        if bit_index >= UNIVERSE {
+            return None;
+        }
+        let (mut key, low) = Self::split(bit_index);
+        let mut from = low as usize;
+        let start = self.keys.partition_point(|container_key| *container_key < key);
+        for index in start..self.keys.len() {
+            if self.keys[index] != key {
+                break;
+            }
+            if let Some(found) = self.containers[index].next_zero(from) {
+                return Some(Self::join(key, found));
+            }
+            key = key.checked_add(1)?;
+            from = 0;
+        }
+        Some(Self::join(key, from as u16))
+    }
+
+    pub fn last_one(&self) -> Option<usize> {
+        let key = *self.keys.last()?;
+        Some(Self::join(key, self.containers.last()?.last_one()?))
+    }
+
+    pub fn iter_ones(&self) -> CompressedIterator<'_> {
+        CompressedIterator {
+            keys: self.keys.iter(),
+            containers: self.containers.iter(),
+            current: None,
+        }
+    }
+
+    pub fn to_indices_true(&self) -> Vec<usize> {
+        self.iter_ones().collect()
+    }
+
+    /// Converts every container to its smallest representation, runs included
+    pub fn optimize(&mut self) {
+        for container in self.containers.iter_mut() {
+            let taken = std::mem::replace(container, Container::Array(Vec::new()));
+            *container = taken.optimize();
+        }
+    }
+
+    pub fn from_bitmap(bitmap: &Bitmap) -> Self {
+        if bitmap.bit_capacity() > UNIVERSE {
+            panic!("Bitmap does not fit into the compressed universe");
+        }
+        let mut compressed = Self::new();
+        let mut lower_bound = 0;
+        while lower_bound < bitmap.bit_capacity() {
+            let upper_bound = (lower_bound + CHUNK_MASK + 1).min(bitmap.bit_capacity());
+            let mut words: Words = Box::new([0; CHUNK_WORDS]);
+            for bit_index in bitmap.iter_ones_range(lower_bound..upper_bound) {
+                let low = bit_index & CHUNK_MASK;
+                words[low >> 6] |= 1 << (low & 63);
+            }
+            if let Some(container) = Container::from_words(words) {
+                compressed.keys.push((lower_bound >> CHUNK_SHIFT) as u16);
+                compressed.containers.push(container.optimize());
+            }
+            lower_bound = upper_bound;
+        }
+        compressed
+    }
+
+    /// Panics if a set bit does not fit into `bit_count` bits
+    pub fn to_bitmap(&self, bit_count: usize) -> Bitmap {
+        if let Some(last) = self.last_one() {
+            if last >= bit_count {
+                panic!("Bit index out of bounds");
+            }
+        }
+        let mut bitmap = Bitmap::new(bit_count);
+        for bit_index in self.iter_ones() {
+            unsafe { bitmap.set_unchecked(bit_index, true) };
+        }
+        bitmap
+    }
+
+    /// Merges the containers by key. `keep_lhs`/`keep_rhs` decide whether a container without
+    /// a partner is copied over, `both` combines two containers with the same key.
+    fn merge(
+        &self,
+        other: &CompressedBitmap,
+        keep_lhs: bool,
+        keep_rhs: bool,
+        both: impl Fn(&Container, &Container) -> Option<Container>,
+    ) -> CompressedBitmap {
+        let mut result = CompressedBitmap::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.keys.len() || j < other.keys.len() {
+            // An exhausted side sorts after every key
+            let lhs_key = self.keys.get(i).map_or(u32::MAX, |key| *key as u32);
+            let rhs_key = other.keys.get(j).map_or(u32::MAX, |key| *key as u32);
+            match lhs_key.cmp(&rhs_key) {
+                Ordering::Equal => {
+                    if let Some(container) = both(&self.containers[i], &other.containers[j]) {
+                        result.keys.push(self.keys[i]);
+                        result.containers.push(container);
+                    }
+                    i += 1;
+                    j += 1;
+                }
+                Ordering::Less => {
+                    if keep_lhs {
+                        result.keys.push(self.keys[i]);
+                        result.containers.push(self.containers[i].clone());
+                    }
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    if keep_rhs {
+                        result.keys.push(other.keys[j]);
+                        result.containers.push(other.containers[j].clone());
+                    }
+                    j += 1;
+                }
+            }
+        }
+        result
+    }
+
+    pub fn and_not(&self, other: &CompressedBitmap) -> CompressedBitmap {
+        self.merge(other, true, false, |lhs, rhs| Container::combine(lhs, rhs, |lhs, rhs| lhs & !rhs))
+    }
+
+    pub fn and_not_assign(&mut self, other: &CompressedBitmap) {
+        *self = self.and_not(other);
+    }
+}
+
+impl BitAnd<&CompressedBitmap> for &CompressedBitmap {
+    type Output = CompressedBitmap;
+    fn bitand(self, rhs: &CompressedBitmap) -> Self::Output {
+        self.merge(rhs, false, false, Container::intersect)
+    }
+}
+
+impl BitOr<&CompressedBitmap> for &CompressedBitmap {
+    type Output = CompressedBitmap;
+    fn bitor(self, rhs: &CompressedBitmap) -> Self::Output {
+        self.merge(rhs, true, true, |lhs, rhs| Container::combine(lhs, rhs, |lhs, rhs| lhs | rhs))
+    }
+}
+
+impl BitXor<&CompressedBitmap> for &CompressedBitmap {
+    type Output = CompressedBitmap;
+    fn bitxor(self, rhs: &CompressedBitmap) -> Self::Output {
+        self.merge(rhs, true, true, |lhs, rhs| Container::combine(lhs, rhs, |lhs, rhs| lhs ^ rhs))
+    }
+}
+
+impl BitAndAssign<&CompressedBitmap> for CompressedBitmap {
+    fn bitand_assign(&mut self, rhs: &CompressedBitmap) {
+        *self = &*self & rhs;
+    }
+}
+
+impl BitOrAssign<&CompressedBitmap> for CompressedBitmap {
+    fn bitor_assign(&mut self, rhs: &CompressedBitmap) {
+        *self = &*self | rhs;
+    }
+}
+
+impl BitXorAssign<&CompressedBitmap> for CompressedBitmap {
+    fn bitxor_assign(&mut self, rhs: &CompressedBitmap) {
+        *self = &*self ^ rhs;
+    }
+}
+
+impl From<&Bitmap> for CompressedBitmap {
+    fn from(bitmap: &Bitmap) -> Self {
+        Self::from_bitmap(bitmap)
+    }
+}
+
+pub struct CompressedIterator<'a> {
+    keys: slice::Iter<'a, u16>,
+    containers: slice::Iter<'a, Container>,
+    current: Option<(u16, ContainerIterator<'a>)>,
+}
+
+impl Iterator for CompressedIterator<'_> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, iterator)) = &mut self.current {
+                if let Some(low) = iterator.next() {
+                    return Some(CompressedBitmap::join(*key, low));
+                }
+                self.current = None;
+            }
+            let key = *self.keys.next()?;
+            self.current = Some((key, self.containers.next()?.iter()));
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use crate::data_structs::array::Array;
-use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, DIV_SHIFT};
+use crate::data_structs::bitmap::atomic_bitmap::AtomicBitmap;
+use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, BIT_MASK, DIV_SHIFT};
+use crate::data_structs::bitmap::Bitmap;
+use crate::data_structs::raw_alloc::RawAlloc;
 use std::collections::HashMap;
 use std::ops::BitOrAssign;
 
+/// Mask of bits inside of a single word, `chunk` is the index of that word
 #[derive(Copy, Clone)]
 pub struct Handle {
     pub bit_mask: usize,
-    pub chunk: u8,
+    pub chunk: usize,
 }
 
 impl BitOrAssign for Handle {
@@ -17,12 +21,13 @@ impl BitOrAssign for Handle {
 }
 
 impl Handle {
+    /// Groups the bit offsets by word, one handle per word
     pub fn new_batch(offsets: &[usize]) -> Array<Self> {
         let mut array = Array::new_default_bytes(offsets.len(), 0);
         let mut len = 0;
-        let mut chunk_table = HashMap::<u8, usize>::new();
+        let mut chunk_table = HashMap::<usize, usize>::new();
         for offset in offsets {
-            let chunk_offset = (offset >> DIV_SHIFT) as u8;
+            let chunk_offset = offset >> DIV_SHIFT;
             let index = match chunk_table.get(&chunk_offset) {
                 Some(index) => *index,
                 None => {
@@ -44,4 +49,31 @@ impl Handle {
         array.resize(len);
         array
     }
+
+    /// Same as `new_batch` but panics if an offset is out of bounds of `bitmap`
+    pub fn new_batch_checked<A: RawAlloc>(offsets: &[usize], bitmap: &Bitmap<A>) -> Array<Self> {
+        Self::new_batch_bounded(offsets, bitmap.bit_capacity())
+    }
+    /// Same as `new_batch_checked` for batches meant for an `AtomicBitmap`
+    pub fn new_atomic_batch_checked(offsets: &[usize], bitmap: &AtomicBitmap) -> Array<Self> {
+        Self::new_batch_bounded(offsets, bitmap.bit_capacity())
+    }
+
+    fn new_batch_bounded(offsets: &[usize], bit_capacity: usize) -> Array<Self> {
+        if offsets.iter().any(|offset| *offset >= bit_capacity) {
+            panic!("Bit index out of bounds");
+        }
+        Self::new_batch(offsets)
+    }
+
+    /// Whether every bit of the handle is below `bit_capacity`
+    #[inline(always)]
+    pub fn fits(&self, bit_capacity: usize) -> bool {
+        let last_chunk = bit_capacity >> DIV_SHIFT;
+        if self.chunk != last_chunk {
+            return self.chunk < last_chunk;
+        }
+        let valid = !(BIT_MASK << (bit_capacity & BIT_END_OFFSET));
+        self.bit_mask & !valid == 0
+    }
 }
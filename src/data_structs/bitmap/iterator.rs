@@ -0,0 +1,101 @@
+use std::marker::PhantomData;
+
+use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, BIT_MASK, DIV_SHIFT};
+
+/// Lazily walks the set bits of a bitmap word by word. Zeros are walked by flipping every loaded word.
+/// The front and back words are kept apart until the two ends meet, then the shared word lives in `front_word`.
+pub struct BitIterator<'a> {
+    pub(crate) phantom_data: PhantomData<&'a usize>,
+    pub(crate) data: *const usize,
+    pub(crate) flip: usize,
+    pub(crate) front_index: usize,
+    pub(crate) front_word: usize,
+    pub(crate) back_index: usize,
+    pub(crate) back_word: usize,
+}
+
+impl BitIterator<'_> {
+    /// `lower_bound` is inclusive, `upper_bound` is exclusive
+    pub(crate) unsafe fn new(data: *const usize, lower_bound: usize, upper_bound: usize, flip: usize) -> Self {
+        let mut iterator = BitIterator {
+            phantom_data: PhantomData,
+            data,
+            flip,
+            front_index: 0,
+            front_word: 0,
+            back_index: 0,
+            back_word: 0,
+        };
+        if lower_bound >= upper_bound {
+            return iterator;
+        }
+
+        let last = upper_bound - 1;
+        let lower_mask = !((1 << (lower_bound & BIT_END_OFFSET)) - 1);
+        let upper_mask = BIT_MASK >> (BIT_END_OFFSET - (last & BIT_END_OFFSET));
+        iterator.front_index = lower_bound >> DIV_SHIFT;
+        iterator.back_index = last >> DIV_SHIFT;
+        if iterator.front_index == iterator.back_index {
+            iterator.front_word = iterator.load(iterator.front_index) & lower_mask & upper_mask;
+        } else {
+            iterator.front_word = iterator.load(iterator.front_index) & lower_mask;
+            iterator.back_word = iterator.load(iterator.back_index) & upper_mask;
+        }
+        iterator
+    }
+
+    #[inline(always)]
+    fn load(&self, index: usize) -> usize {
+        unsafe { *self.data.add(index) ^ self.flip }
+    }
+}
+
+impl Iterator for BitIterator<'_> {
+    type Item = usize;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.front_word != 0 {
+                let bit = self.front_word.trailing_zeros() as usize;
+                // Clear the lowest set bit
+                self.front_word &= self.front_word - 1;
+                return Some((self.front_index << DIV_SHIFT) + bit);
+            }
+            if self.front_index >= self.back_index {
+                return None;
+            }
+            self.front_index += 1;
+            if self.front_index == self.back_index {
+                self.front_word = self.back_word;
+                self.back_word = 0;
+            } else {
+                self.front_word = self.load(self.front_index);
+            }
+        }
+    }
+}
+
+impl DoubleEndedIterator for BitIterator<'_> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let word = if self.front_index == self.back_index {
+                &mut self.front_word
+            } else {
+                &mut self.back_word
+            };
+            if *word != 0 {
+                let bit = BIT_END_OFFSET - word.leading_zeros() as usize;
+                *word &= !(1 << bit);
+                return Some((self.back_index << DIV_SHIFT) + bit);
+            }
+            if self.front_index >= self.back_index {
+                return None;
+            }
+            self.back_index -= 1;
+            if self.back_index != self.front_index {
+                self.back_word = self.load(self.back_index);
+            }
+        }
+    }
+}
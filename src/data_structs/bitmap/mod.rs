@@ -1,11 +1,23 @@
 use std::alloc::Layout;
+use std::ops::{Bound, RangeBounds};
 use std::ptr;
 
 use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, BIT_MASK, DIV_SHIFT};
 use crate::data_structs::bitmap::handle::Handle;
+use crate::data_structs::bitmap::iterator::BitIterator;
+use crate::data_structs::raw_alloc::{Global, RawAlloc};
 
 pub mod atomic_bitmap;
+pub mod compressed;
 pub mod handle;
+pub mod iterator;
+pub mod query;
+pub mod rank_select;
+pub mod slot_allocator;
+pub mod view;
+mod binary;
+mod ops;
+mod range;
 
 #[cfg(target_pointer_width = "64")]
 pub(crate) mod consts {
@@ -28,24 +40,143 @@ pub(self) mod consts {
     pub(crate) const BIT_MASK: usize = 0xFFFF;
 }
 
-pub struct Bitmap {
+pub struct Bitmap<A = Global>
+where
+    A: RawAlloc,
+{
     data: *mut usize,
     bit_capacity: usize,
     capacity: usize,
     layout: Layout,
+    alloc: A,
 }
 
 impl Bitmap {
     pub fn new(bit_count: usize) -> Self {
+        Self::new_in(bit_count, Global)
+    }
+    #[inline(always)]
+    pub fn try_new(bit_count: usize) -> Option<Self> {
+        Self::try_new_in(bit_count, Global)
+    }
+
+    /// Creates an empty bitmap with room for `bit_count` bits
+    pub fn with_capacity(bit_count: usize) -> Self {
+        Self::with_capacity_in(bit_count, Global)
+    }
+}
+
+impl<A> Bitmap<A>
+where
+    A: RawAlloc,
+{
+    pub fn new_in(bit_count: usize, alloc: A) -> Self {
+        if Layout::array::<usize>((bit_count >> DIV_SHIFT) + 1).is_err() {
+            panic!("Failed to create layout");
+        }
+        Self::try_new_in(bit_count, alloc).expect("Failed to allocate memory")
+    }
+    /// Same as `new_in` but returns None instead of panicking when the layout overflows or the allocation fails
+    pub fn try_new_in(bit_count: usize, alloc: A) -> Option<Self> {
         let size = (bit_count >> DIV_SHIFT) + 1;
-        let layout = Layout::array::<usize>(size).expect("Failed to create layout");
-        let data = unsafe { std::alloc::alloc(layout) as *mut usize };
+        let layout = Layout::array::<usize>(size).ok()?;
+        let data = unsafe { alloc.alloc(layout) as *mut usize };
+        if data.is_null() {
+            return None;
+        }
         unsafe { ptr::write_bytes(data, 0, size) };
-        Bitmap {
+        Some(Bitmap {
             data,
             capacity: size,
             bit_capacity: bit_count,
             layout,
+            alloc,
+        })
+    }
+
+    pub fn with_capacity_in(bit_count: usize, alloc: A) -> Self {
+        let mut bitmap = Self::new_in(bit_count, alloc);
+        bitmap.bit_capacity = 0;
+        bitmap
+    }
+
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Words that hold bits below bit_capacity. Words past these are always zero.
+    #[inline(always)]
+    pub(crate) fn used_words(&self) -> usize {
+        (self.bit_capacity >> DIV_SHIFT) + 1
+    }
+
+    /// Bits of the last used word that are past bit_capacity have to stay zero so counts and scans stay correct
+    #[inline(always)]
+    pub(crate) fn clear_tail(&mut self) {
+        let tail_bits = self.bit_capacity & BIT_END_OFFSET;
+        unsafe { *self.data.add(self.bit_capacity >> DIV_SHIFT) &= (1 << tail_bits) - 1 };
+    }
+
+    /// Reallocates to exactly `words` words, new words are zeroed
+    fn realloc_words(&mut self, words: usize) {
+        let new_layout = Layout::array::<usize>(words).expect("Failed to create layout");
+        unsafe {
+            let data = self.alloc.realloc(self.data as *mut u8, self.layout, new_layout.size()) as *mut usize;
+            if data.is_null() {
+                panic!("Failed to reallocate memory");
+            }
+            if words > self.capacity {
+                ptr::write_bytes(data.add(self.capacity), 0, words - self.capacity);
+            }
+            self.data = data;
+        }
+        self.capacity = words;
+        self.layout = new_layout;
+    }
+
+    /// Grows or shrinks the bitmap. New bits are set to `fill`, the allocation is only ever grown.
+    pub fn resize(&mut self, new_bit_count: usize, fill: bool) {
+        let old_bit_count = self.bit_capacity;
+        if new_bit_count <= old_bit_count {
+            let old_words = self.used_words();
+            self.bit_capacity = new_bit_count;
+            self.clear_tail();
+            let words = self.used_words();
+            unsafe { ptr::write_bytes(self.data.add(words), 0, old_words - words) };
+            return;
+        }
+
+        let words = (new_bit_count >> DIV_SHIFT) + 1;
+        if words > self.capacity {
+            self.realloc_words(words);
+        }
+        self.bit_capacity = new_bit_count;
+        if fill {
+            unsafe { self.set_range_unchecked(old_bit_count, new_bit_count, true) };
+        }
+    }
+    #[inline(always)]
+    pub fn truncate(&mut self, bit_count: usize) {
+        if bit_count < self.bit_capacity {
+            self.resize(bit_count, false);
+        }
+    }
+    /// Appends a bit, doubling the allocation when it runs out
+    #[inline(always)]
+    pub fn push(&mut self, value: bool) {
+        let bit_index = self.bit_capacity;
+        if (bit_index + 1) >> DIV_SHIFT >= self.capacity {
+            self.realloc_words(self.capacity * 2);
+        }
+        self.bit_capacity += 1;
+        unsafe { self.set_unchecked(bit_index, value) };
+    }
+    /// Releases the words that are not needed for the current bit_capacity
+    pub fn shrink_to_fit(&mut self) {
+        let words = self.used_words();
+        if words < self.capacity {
+            self.realloc_words(words);
         }
     }
 
@@ -97,6 +228,10 @@ impl Bitmap {
         unsafe { self.count_ones_unchecked(lower_bound, upper_bound - 1) }
     }
 
+    /// Counts the unset bits in `lower_bound..=upper_bound`
+    ///
+    /// # Safety
+    /// `lower_bound` must be at most `upper_bound` and `upper_bound` less than `bit_capacity()`.
     pub unsafe fn count_zeros_unchecked(&self, lower_bound: usize, upper_bound: usize) -> usize {
         let lower_offset = lower_bound >> DIV_SHIFT;
         let lower_bit_offset = lower_bound & BIT_END_OFFSET;
@@ -130,6 +265,10 @@ impl Bitmap {
         counter
     }
 
+    /// Counts the set bits in `lower_bound..=upper_bound`
+    ///
+    /// # Safety
+    /// `lower_bound` must be at most `upper_bound` and `upper_bound` less than `bit_capacity()`.
     pub unsafe fn count_ones_unchecked(&self, lower_bound: usize, upper_bound: usize) -> usize {
         let lower_offset = lower_bound >> DIV_SHIFT;
         let lower_bit_offset = lower_bound & BIT_END_OFFSET;
@@ -234,6 +373,9 @@ impl Bitmap {
         }
     }
     /// Returns higher > bit_capacity in case of not found
+    ///
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
     pub unsafe fn first_zero_unchecked(&self, bit_index: usize) -> usize {
         let offset = bit_index >> DIV_SHIFT;
         let bit_offset = bit_index & BIT_END_OFFSET;
@@ -258,6 +400,9 @@ impl Bitmap {
     }
 
     /// Returns higher > bit_capacity in case of not found
+    ///
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
     pub unsafe fn first_one_unchecked(&self, bit_index: usize) -> usize {
         let offset = bit_index >> DIV_SHIFT;
         let bit_offset = bit_index & BIT_END_OFFSET;
@@ -280,24 +425,206 @@ impl Bitmap {
         counter += data.trailing_zeros() as usize;
         counter
     }
+    #[inline(always)]
+    pub fn last_one(&self) -> Option<usize> {
+        if self.bit_capacity == 0 {
+            return None;
+        }
+        unsafe { self.last_one_unchecked(self.bit_capacity - 1) }
+    }
+    #[inline(always)]
+    pub fn last_zero(&self) -> Option<usize> {
+        if self.bit_capacity == 0 {
+            return None;
+        }
+        unsafe { self.last_zero_unchecked(self.bit_capacity - 1) }
+    }
+    /// Highest set bit strictly below `bit_index`
+    #[inline(always)]
+    pub fn prev_one(&self, bit_index: usize) -> Option<usize> {
+        if bit_index > self.bit_capacity {
+            panic!("Bit index out of bounds");
+        }
+        if bit_index == 0 {
+            return None;
+        }
+        unsafe { self.last_one_unchecked(bit_index - 1) }
+    }
+    /// Highest unset bit strictly below `bit_index`
+    #[inline(always)]
+    pub fn prev_zero(&self, bit_index: usize) -> Option<usize> {
+        if bit_index > self.bit_capacity {
+            panic!("Bit index out of bounds");
+        }
+        if bit_index == 0 {
+            return None;
+        }
+        unsafe { self.last_zero_unchecked(bit_index - 1) }
+    }
+    #[inline(always)]
+    pub fn last_one_bounds(&self, lower_bound: usize, upper_bound: usize) -> Option<usize> {
+        if upper_bound == 0 {
+            panic!("Upper bound cannot be zero");
+        }
+
+        if lower_bound > upper_bound {
+            panic!("Lower bound cannot be greater than upper bound");
+        }
+
+        if lower_bound >= self.bit_capacity {
+            panic!("Bit index out of bounds");
+        }
+        if upper_bound > self.bit_capacity {
+            panic!("Upper bound out of bounds");
+        }
+        unsafe { self.last_bounds_unchecked(lower_bound, upper_bound, 0) }
+    }
+    #[inline(always)]
+    pub fn last_zero_bounds(&self, lower_bound: usize, upper_bound: usize) -> Option<usize> {
+        if upper_bound == 0 {
+            panic!("Upper bound cannot be zero");
+        }
+
+        if lower_bound > upper_bound {
+            panic!("Lower bound cannot be greater than upper bound");
+        }
+
+        if lower_bound >= self.bit_capacity {
+            panic!("Bit index out of bounds");
+        }
+        if upper_bound > self.bit_capacity {
+            panic!("Upper bound out of bounds");
+        }
+        unsafe { self.last_bounds_unchecked(lower_bound, upper_bound, BIT_MASK) }
+    }
+    /// Highest set bit at or below `bit_index`
+    ///
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
+    #[inline(always)]
+    pub unsafe fn last_one_unchecked(&self, bit_index: usize) -> Option<usize> {
+        self.last_bounds_unchecked(0, bit_index + 1, 0)
+    }
+    /// Highest unset bit at or below `bit_index`
+    ///
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
+    #[inline(always)]
+    pub unsafe fn last_zero_unchecked(&self, bit_index: usize) -> Option<usize> {
+        self.last_bounds_unchecked(0, bit_index + 1, BIT_MASK)
+    }
+    /// Backward scan over `lower_bound..upper_bound` that stops at the word holding `lower_bound`.
+    /// `flip` is `BIT_MASK` to look for unset bits.
+    unsafe fn last_bounds_unchecked(&self, lower_bound: usize, upper_bound: usize, flip: usize) -> Option<usize> {
+        if lower_bound >= upper_bound {
+            return None;
+        }
+        let lower_offset = lower_bound >> DIV_SHIFT;
+        let mut offset = (upper_bound - 1) >> DIV_SHIFT;
+        let mut data = (*self.data.add(offset) ^ flip) & (BIT_MASK >> (BIT_END_OFFSET - ((upper_bound - 1) & BIT_END_OFFSET)));
+        loop {
+            if offset == lower_offset {
+                data &= BIT_MASK << (lower_bound & BIT_END_OFFSET);
+            }
+            if data != 0 {
+                return Some((offset << DIV_SHIFT) + BIT_END_OFFSET - data.leading_zeros() as usize);
+            }
+            if offset == lower_offset {
+                return None;
+            }
+            offset -= 1;
+            data = *self.data.add(offset) ^ flip;
+        }
+    }
+    /// Whether every bit of every handle is set
     pub fn check_batch(&self, handles: &[Handle]) -> bool {
         for handle in handles {
-            let val = unsafe { *self.data.add(handle.chunk as usize) };
+            self.check_handle(handle);
+            let val = unsafe { *self.data.add(handle.chunk) };
             if (val & handle.bit_mask) != handle.bit_mask {
                 return false;
             }
         }
         true
     }
-
-    pub fn to_indices_true(&self) -> Vec<usize> {
-        let mut indices = Vec::new();
-        for i in 0..self.bit_capacity {
-            if unsafe { self.get_unchecked(i) } {
-                indices.push(i);
+    /// Whether any bit of any handle is set
+    pub fn check_batch_any(&self, handles: &[Handle]) -> bool {
+        for handle in handles {
+            self.check_handle(handle);
+            if unsafe { *self.data.add(handle.chunk) } & handle.bit_mask != 0 {
+                return true;
             }
         }
-        indices
+        false
+    }
+    pub fn set_batch(&mut self, handles: &[Handle]) {
+        for handle in handles {
+            self.check_handle(handle);
+            unsafe { *self.data.add(handle.chunk) |= handle.bit_mask };
+        }
+    }
+    pub fn clear_batch(&mut self, handles: &[Handle]) {
+        for handle in handles {
+            self.check_handle(handle);
+            unsafe { *self.data.add(handle.chunk) &= !handle.bit_mask };
+        }
+    }
+    pub fn toggle_batch(&mut self, handles: &[Handle]) {
+        for handle in handles {
+            self.check_handle(handle);
+            unsafe { *self.data.add(handle.chunk) ^= handle.bit_mask };
+        }
+    }
+    #[inline(always)]
+    fn check_handle(&self, handle: &Handle) {
+        if !handle.fits(self.bit_capacity) {
+            panic!("Handle out of bounds");
+        }
+    }
+
+
+    /// Resolves the range into an inclusive lower and exclusive upper bound
+    pub(crate) fn resolve_range(&self, range: impl RangeBounds<usize>) -> (usize, usize) {
+        let lower_bound = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => *start + 1,
+            Bound::Unbounded => 0,
+        };
+        let upper_bound = match range.end_bound() {
+            Bound::Included(end) => *end + 1,
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.bit_capacity,
+        };
+        if lower_bound > upper_bound {
+            panic!("Lower bound cannot be greater than upper bound");
+        }
+        if upper_bound > self.bit_capacity {
+            panic!("Upper bound out of bounds");
+        }
+        (lower_bound, upper_bound)
+    }
+
+    #[inline(always)]
+    pub fn iter_ones(&self) -> BitIterator<'_> {
+        unsafe { BitIterator::new(self.data, 0, self.bit_capacity, 0) }
+    }
+    #[inline(always)]
+    pub fn iter_zeros(&self) -> BitIterator<'_> {
+        unsafe { BitIterator::new(self.data, 0, self.bit_capacity, BIT_MASK) }
+    }
+    #[inline(always)]
+    pub fn iter_ones_range(&self, range: impl RangeBounds<usize>) -> BitIterator<'_> {
+        let (lower_bound, upper_bound) = self.resolve_range(range);
+        unsafe { BitIterator::new(self.data, lower_bound, upper_bound, 0) }
+    }
+    #[inline(always)]
+    pub fn iter_zeros_range(&self, range: impl RangeBounds<usize>) -> BitIterator<'_> {
+        let (lower_bound, upper_bound) = self.resolve_range(range);
+        unsafe { BitIterator::new(self.data, lower_bound, upper_bound, BIT_MASK) }
+    }
+
+    pub fn to_indices_true(&self) -> Vec<usize> {
+        self.iter_ones().collect()
     }
 
     pub fn to_indices_true_bounded(&self, start: usize, end: usize) -> Vec<usize> {
@@ -307,24 +634,11 @@ impl Bitmap {
         if end > self.bit_capacity {
             panic!("End must be less than or equal to bit capacity");
         }
-
-        let mut indices = Vec::new();
-        for i in start..end {
-            if unsafe { self.get_unchecked(i) } {
-                indices.push(i);
-            }
-        }
-        indices
+        self.iter_ones_range(start..end).collect()
     }
 
     pub fn to_indices_false(&self) -> Vec<usize> {
-        let mut indices = Vec::new();
-        for i in 0..self.bit_capacity {
-            if unsafe { self.get_unchecked(i) == false } {
-                indices.push(i);
-            }
-        }
-        indices
+        self.iter_zeros().collect()
     }
 
     pub fn to_indices_false_bounded(&self, start: usize, end: usize) -> Vec<usize> {
@@ -334,14 +648,7 @@ impl Bitmap {
         if end > self.bit_capacity {
             panic!("End must be less than or equal to bit capacity");
         }
-
-        let mut indices = Vec::new();
-        for i in start..end {
-            if unsafe { self.get_unchecked(i) == false } {
-                indices.push(i);
-            }
-        }
-        indices
+        self.iter_zeros_range(start..end).collect()
     }
 
     #[inline(always)]
@@ -378,6 +685,8 @@ impl Bitmap {
             Some((*ptr & (1 << bit_offset)) != 0)
         }
     }
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
     #[inline(always)]
     pub unsafe fn set_unchecked(&mut self, bit_index: usize, value: bool) {
         let offset = bit_index >> DIV_SHIFT;
@@ -387,6 +696,8 @@ impl Bitmap {
             *ptr = (*ptr & !(1 << bit_offset)) | ((value as usize) << bit_offset);
         }
     }
+    /// # Safety
+    /// `bit_index` must be less than `bit_capacity()`.
     #[inline(always)]
     pub unsafe fn get_unchecked(&self, bit_index: usize) -> bool {
         let offset = bit_index >> DIV_SHIFT;
@@ -398,10 +709,24 @@ impl Bitmap {
     }
 }
 
-impl Drop for Bitmap {
+impl<A> Clone for Bitmap<A>
+where
+    A: RawAlloc + Clone,
+{
+    fn clone(&self) -> Self {
+        let bitmap = Bitmap::new_in(self.bit_capacity, self.alloc.clone());
+        unsafe { ptr::copy_nonoverlapping(self.data, bitmap.data, self.used_words()) };
+        bitmap
+    }
+}
+
+impl<A> Drop for Bitmap<A>
+where
+    A: RawAlloc,
+{
     fn drop(&mut self) {
         unsafe {
-            std::alloc::dealloc(self.data as *mut u8, self.layout);
+            self.alloc.dealloc(self.data as *mut u8, self.layout);
         }
     }
 }
@@ -0,0 +1,113 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use std::ptr;
+
+use crate::data_structs::bitmap::Bitmap;
+use crate::data_structs::raw_alloc::RawAlloc;
+
+/// Word wise set algebra. Words missing from the shorter bitmap are treated as zeros.
+/// The in-place operators keep the length of the left side, the allocating ones
+/// produce a bitmap as long as the longer operand.
+impl<A> Bitmap<A>
+where
+    A: RawAlloc,
+{
+    #[inline(always)]
+    fn apply_assign<B: RawAlloc>(&mut self, other: &Bitmap<B>, op: impl Fn(usize, usize) -> usize) {
+        let words = self.used_words();
+        let shared = words.min(other.used_words());
+        unsafe {
+            for i in 0..shared {
+                let ptr = self.data.add(i);
+                *ptr = op(*ptr, *other.data.add(i));
+            }
+            for i in shared..words {
+                let ptr = self.data.add(i);
+                *ptr = op(*ptr, 0);
+            }
+        }
+        self.clear_tail();
+    }
+
+    /// Clears every bit that is set in `other`
+    #[inline(always)]
+    pub fn and_not_assign<B: RawAlloc>(&mut self, other: &Bitmap<B>) {
+        self.apply_assign(other, |lhs, rhs| lhs & !rhs);
+    }
+
+    /// Flips every bit in place
+    pub fn negate(&mut self) {
+        for i in 0..self.used_words() {
+            unsafe {
+                let ptr = self.data.add(i);
+                *ptr = !*ptr;
+            }
+        }
+        self.clear_tail();
+    }
+}
+
+/// The allocating operators put the result in a clone of the left side's allocator
+impl<A> Bitmap<A>
+where
+    A: RawAlloc + Clone,
+{
+    /// Copy of self with at least `bit_count` bits
+    fn widened_clone(&self, bit_count: usize) -> Bitmap<A> {
+        if bit_count <= self.bit_capacity {
+            return self.clone();
+        }
+        let bitmap = Bitmap::new_in(bit_count, self.alloc.clone());
+        unsafe { ptr::copy_nonoverlapping(self.data, bitmap.data, self.used_words()) };
+        bitmap
+    }
+
+    pub fn and_not<B: RawAlloc>(&self, other: &Bitmap<B>) -> Bitmap<A> {
+        let mut bitmap = self.widened_clone(other.bit_capacity);
+        bitmap.and_not_assign(other);
+        bitmap
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $operator:tt) => {
+        impl<A, B> $assign<&Bitmap<B>> for Bitmap<A>
+        where
+            A: RawAlloc,
+            B: RawAlloc,
+        {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: &Bitmap<B>) {
+                self.apply_assign(rhs, |lhs, rhs| lhs $operator rhs);
+            }
+        }
+
+        impl<A, B> $op<&Bitmap<B>> for &Bitmap<A>
+        where
+            A: RawAlloc + Clone,
+            B: RawAlloc,
+        {
+            type Output = Bitmap<A>;
+            fn $op_fn(self, rhs: &Bitmap<B>) -> Self::Output {
+                let mut bitmap = self.widened_clone(rhs.bit_capacity);
+                bitmap.$assign_fn(rhs);
+                bitmap
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl<A> Not for &Bitmap<A>
+where
+    A: RawAlloc + Clone,
+{
+    type Output = Bitmap<A>;
+    fn not(self) -> Self::Output {
+        let mut bitmap = self.clone();
+        bitmap.negate();
+        bitmap
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use crate::data_structs::array::Array;
+use crate::data_structs::bitmap::atomic_bitmap::{AtomicBitmap, Mode};
+use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, DIV_SHIFT};
+use crate::data_structs::bitmap::Bitmap;
+use crate::data_structs::raw_alloc::RawAlloc;
+
+/// Masks of a single word, `chunk` is the index of that word
+#[derive(Copy, Clone, Default)]
+pub struct QueryWord {
+    pub chunk: usize,
+    pub required: usize,
+    pub forbidden: usize,
+    pub any_of: usize,
+}
+
+/// Predicate over a bitmap: every required bit is set, no forbidden bit is set and, if there are
+/// any-of bits, at least one of them is set. The masks are grouped per word and sorted by word.
+pub struct BitQuery {
+    words: Array<QueryWord>,
+    has_any_of: bool,
+    /// One past the highest referenced bit
+    bit_len: usize,
+}
+
+impl BitQuery {
+    pub fn new(required: &[usize], forbidden: &[usize], any_of: &[usize]) -> Self {
+        let mut table = BTreeMap::<usize, QueryWord>::new();
+        let mut add = |offsets: &[usize], select: fn(&mut QueryWord) -> &mut usize| {
+            for offset in offsets {
+                let chunk = offset >> DIV_SHIFT;
+                let word = table.entry(chunk).or_insert(QueryWord {
+                    chunk,
+                    ..QueryWord::default()
+                });
+                *select(word) |= 1 << (offset & BIT_END_OFFSET);
+            }
+        };
+        add(required, |word| &mut word.required);
+        add(forbidden, |word| &mut word.forbidden);
+        add(any_of, |word| &mut word.any_of);
+
+        if table.values().any(|word| word.required & word.forbidden != 0) {
+            panic!("Bit cannot be both required and forbidden");
+        }
+        let bit_len = required.iter().chain(forbidden).chain(any_of).max().map_or(0, |max| max + 1);
+        BitQuery {
+            words: Array::from_vec(table.into_values().collect()),
+            has_any_of: !any_of.is_empty(),
+            bit_len,
+        }
+    }
+
+    /// Query that only requires bits, same as a `Handle` batch
+    pub fn all_of(required: &[usize]) -> Self {
+        Self::new(required, &[], &[])
+    }
+    pub fn none_of(forbidden: &[usize]) -> Self {
+        Self::new(&[], forbidden, &[])
+    }
+    pub fn any_of(any_of: &[usize]) -> Self {
+        Self::new(&[], &[], any_of)
+    }
+
+    #[inline(always)]
+    pub fn words(&self) -> &[QueryWord] {
+        self.words.as_slice()
+    }
+    /// Smallest `bit_capacity` a bitmap needs for the query to be evaluated on it
+    #[inline(always)]
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    /// Exits on the first word that fails a required or forbidden mask
+    #[inline(always)]
+    fn evaluate(&self, load: impl Fn(usize) -> usize) -> bool {
+        let mut any_hit = !self.has_any_of;
+        for word in self.words.iter() {
+            let data = load(word.chunk);
+            if data & word.required != word.required || data & word.forbidden != 0 {
+                return false;
+            }
+            any_hit |= data & word.any_of != 0;
+        }
+        any_hit
+    }
+
+    #[inline(always)]
+    fn check_bounds(&self, bit_capacity: usize) {
+        if self.bit_len > bit_capacity {
+            panic!("Query out of bounds");
+        }
+    }
+}
+
+impl<A> Bitmap<A>
+where
+    A: RawAlloc,
+{
+    pub fn matches(&self, query: &BitQuery) -> bool {
+        query.check_bounds(self.bit_capacity);
+        query.evaluate(|chunk| unsafe { *self.data.add(chunk) })
+    }
+}
+
+impl AtomicBitmap {
+    /// Each word is loaded separately, the words are not read as one consistent state
+    pub fn matches(&self, query: &BitQuery, mode: Mode) -> bool {
+        query.check_bounds(self.bit_capacity());
+        query.evaluate(|chunk| unsafe { self.word_unchecked(chunk) }.load(mode.load_ordering()))
+    }
+}
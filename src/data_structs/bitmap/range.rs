@@ -0,0 +1,134 @@
+use std::ops::RangeBounds;
+use std::ptr;
+
+use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, BIT_MASK, DIV_SHIFT};
+use crate::data_structs::bitmap::Bitmap;
+use crate::data_structs::raw_alloc::RawAlloc;
+
+const WORD_BITS: usize = BIT_END_OFFSET + 1;
+
+/// Contiguous range operations. The edge words are masked, the words in between are written whole.
+impl<A> Bitmap<A>
+where
+    A: RawAlloc,
+{
+    #[inline(always)]
+    pub fn flip(&mut self, bit_index: usize) {
+        if bit_index >= self.bit_capacity {
+            panic!("Bit index out of bounds");
+        }
+        unsafe { *self.data.add(bit_index >> DIV_SHIFT) ^= 1 << (bit_index & BIT_END_OFFSET) };
+    }
+
+    pub fn set_range(&mut self, range: impl RangeBounds<usize>, value: bool) {
+        let (lower_bound, upper_bound) = self.resolve_range(range);
+        if lower_bound == upper_bound {
+            return;
+        }
+        unsafe { self.set_range_unchecked(lower_bound, upper_bound, value) }
+    }
+
+    pub fn flip_range(&mut self, range: impl RangeBounds<usize>) {
+        let (lower_bound, upper_bound) = self.resolve_range(range);
+        if lower_bound == upper_bound {
+            return;
+        }
+        unsafe { self.flip_range_unchecked(lower_bound, upper_bound) }
+    }
+
+    /// Sets every bit in `lower_bound..upper_bound`
+    ///
+    /// # Safety
+    /// `lower_bound` must be less than `upper_bound` and `upper_bound` at most `bit_capacity()`.
+    pub unsafe fn set_range_unchecked(&mut self, lower_bound: usize, upper_bound: usize, value: bool) {
+        let lower_offset = lower_bound >> DIV_SHIFT;
+        let upper_offset = (upper_bound - 1) >> DIV_SHIFT;
+        let lower_mask = BIT_MASK << (lower_bound & BIT_END_OFFSET);
+        let upper_mask = BIT_MASK >> (BIT_END_OFFSET - ((upper_bound - 1) & BIT_END_OFFSET));
+        let apply = |ptr: *mut usize, mask: usize| {
+            if value {
+                *ptr |= mask;
+            } else {
+                *ptr &= !mask;
+            }
+        };
+
+        if lower_offset == upper_offset {
+            apply(self.data.add(lower_offset), lower_mask & upper_mask);
+            return;
+        }
+        apply(self.data.add(lower_offset), lower_mask);
+        let fill = if value { 0xFF } else { 0 };
+        ptr::write_bytes(self.data.add(lower_offset + 1), fill, upper_offset - lower_offset - 1);
+        apply(self.data.add(upper_offset), upper_mask);
+    }
+
+    /// Flips every bit in `lower_bound..upper_bound`
+    ///
+    /// # Safety
+    /// `lower_bound` must be less than `upper_bound` and `upper_bound` at most `bit_capacity()`.
+    pub unsafe fn flip_range_unchecked(&mut self, lower_bound: usize, upper_bound: usize) {
+        let lower_offset = lower_bound >> DIV_SHIFT;
+        let upper_offset = (upper_bound - 1) >> DIV_SHIFT;
+        let lower_mask = BIT_MASK << (lower_bound & BIT_END_OFFSET);
+        let upper_mask = BIT_MASK >> (BIT_END_OFFSET - ((upper_bound - 1) & BIT_END_OFFSET));
+
+        if lower_offset == upper_offset {
+            *self.data.add(lower_offset) ^= lower_mask & upper_mask;
+            return;
+        }
+        *self.data.add(lower_offset) ^= lower_mask;
+        for offset in lower_offset + 1..upper_offset {
+            let ptr = self.data.add(offset);
+            *ptr = !*ptr;
+        }
+        *self.data.add(upper_offset) ^= upper_mask;
+    }
+
+    /// Copies the bits of `src_range` in `src` so they start at `dst_offset` in self
+    pub fn copy_range_from<B: RawAlloc>(&mut self, src: &Bitmap<B>, src_range: impl RangeBounds<usize>, dst_offset: usize) {
+        let (lower_bound, upper_bound) = src.resolve_range(src_range);
+        let len = upper_bound - lower_bound;
+        let dst_upper_bound = dst_offset.checked_add(len).expect("Upper bound out of bounds");
+        if dst_upper_bound > self.bit_capacity {
+            panic!("Upper bound out of bounds");
+        }
+
+        let mut copied = 0;
+        while copied < len {
+            let chunk = (len - copied).min(WORD_BITS);
+            unsafe {
+                let bits = src.read_bits(lower_bound + copied, chunk);
+                self.write_bits(dst_offset + copied, chunk, bits);
+            }
+            copied += chunk;
+        }
+    }
+
+    /// Reads `len` (1..=word size) bits starting at `bit_index` into the low bits of the result
+    #[inline(always)]
+    unsafe fn read_bits(&self, bit_index: usize, len: usize) -> usize {
+        let offset = bit_index >> DIV_SHIFT;
+        let bit_offset = bit_index & BIT_END_OFFSET;
+        let mut bits = *self.data.add(offset) >> bit_offset;
+        if bit_offset + len > WORD_BITS {
+            bits |= *self.data.add(offset + 1) << (WORD_BITS - bit_offset);
+        }
+        bits & (BIT_MASK >> (WORD_BITS - len))
+    }
+
+    /// Writes the low `len` (1..=word size) bits of `bits` starting at `bit_index`
+    #[inline(always)]
+    unsafe fn write_bits(&mut self, bit_index: usize, len: usize, bits: usize) {
+        let offset = bit_index >> DIV_SHIFT;
+        let bit_offset = bit_index & BIT_END_OFFSET;
+        let mask = BIT_MASK >> (WORD_BITS - len);
+        let ptr = self.data.add(offset);
+        *ptr = (*ptr & !(mask << bit_offset)) | (bits << bit_offset);
+        if bit_offset + len > WORD_BITS {
+            let shift = WORD_BITS - bit_offset;
+            let ptr = self.data.add(offset + 1);
+            *ptr = (*ptr & !(mask >> shift)) | (bits >> shift);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::data_structs::array::Array;
+use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, DIV_SHIFT};
+use crate::data_structs::bitmap::Bitmap;
+
+const SUPERBLOCK_SHIFT: usize = 3; // 8 words per superblock
+const SUPERBLOCK_WORDS: usize = 1 << SUPERBLOCK_SHIFT;
+
+/// Rank/select directory over a frozen bitmap. Superblocks store the absolute number of ones
+/// before them, every word stores the number of ones before it inside its superblock.
+pub struct RankSelectBitmap {
+    bitmap: Bitmap,
+    superblock_ranks: Array<usize>,
+    block_ranks: Array<u16>,
+    ones: usize,
+}
+
+impl RankSelectBitmap {
+    pub fn new(bitmap: Bitmap) -> Self {
+        let words = bitmap.used_words();
+        let superblocks = (words + SUPERBLOCK_WORDS - 1) >> SUPERBLOCK_SHIFT;
+        let mut superblock_ranks = Array::new_default_bytes(superblocks, 0);
+        let mut block_ranks = Array::new_default_bytes(words, 0);
+
+        let mut ones = 0;
+        let mut superblock_ones = 0;
+        for word in 0..words {
+            if word & (SUPERBLOCK_WORDS - 1) == 0 {
+                superblock_ranks[word >> SUPERBLOCK_SHIFT] = ones;
+                superblock_ones = 0;
+            }
+            block_ranks[word] = superblock_ones as u16;
+            let count = unsafe { *bitmap.data.add(word) }.count_ones() as usize;
+            superblock_ones += count;
+            ones += count;
+        }
+
+        RankSelectBitmap {
+            bitmap,
+            superblock_ranks,
+            block_ranks,
+            ones,
+        }
+    }
+
+    #[inline(always)]
+    pub fn bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+    /// Drops the directory and hands the bitmap back so it can be modified again
+    #[inline(always)]
+    pub fn into_inner(self) -> Bitmap {
+        self.bitmap
+    }
+    #[inline(always)]
+    pub fn bit_capacity(&self) -> usize {
+        self.bitmap.bit_capacity
+    }
+    #[inline(always)]
+    pub fn count_ones(&self) -> usize {
+        self.ones
+    }
+    #[inline(always)]
+    pub fn count_zeros(&self) -> usize {
+        self.bitmap.bit_capacity - self.ones
+    }
+    #[inline(always)]
+    pub fn get(&self, bit_index: usize) -> Option<bool> {
+        self.bitmap.get(bit_index)
+    }
+
+    /// Number of set bits before `bit_index`
+    #[inline(always)]
+    pub fn rank1(&self, bit_index: usize) -> usize {
+        if bit_index > self.bitmap.bit_capacity {
+            panic!("Bit index out of bounds");
+        }
+        unsafe { self.rank1_unchecked(bit_index) }
+    }
+    /// Number of unset bits before `bit_index`
+    #[inline(always)]
+    pub fn rank0(&self, bit_index: usize) -> usize {
+        bit_index - self.rank1(bit_index)
+    }
+    /// # Safety
+    /// `bit_index` must be at most `bit_capacity()`.
+    #[inline(always)]
+    pub unsafe fn rank1_unchecked(&self, bit_index: usize) -> usize {
+        let word = bit_index >> DIV_SHIFT;
+        let bits = *self.bitmap.data.add(word) & ((1 << (bit_index & BIT_END_OFFSET)) - 1);
+        *self.superblock_ranks.index_unchecked(word >> SUPERBLOCK_SHIFT)
+            + *self.block_ranks.index_unchecked(word) as usize
+            + bits.count_ones() as usize
+    }
+
+    /// Position of the `rank`-th set bit, counting from zero
+    pub fn select1(&self, rank: usize) -> Option<usize> {
+        if rank >= self.ones {
+            return None;
+        }
+        Some(self.select(rank, true))
+    }
+    /// Position of the `rank`-th unset bit, counting from zero
+    pub fn select0(&self, rank: usize) -> Option<usize> {
+        if rank >= self.count_zeros() {
+            return None;
+        }
+        Some(self.select(rank, false))
+    }
+
+    /// Number of searched bits (ones or zeros) before the word
+    #[inline(always)]
+    fn counted_before(word: usize, ones_before: usize, ones: bool) -> usize {
+        if ones {
+            ones_before
+        } else {
+            (word << DIV_SHIFT) - ones_before
+        }
+    }
+
+    fn select(&self, rank: usize, ones: bool) -> usize {
+        // Last superblock that starts with at most `rank` searched bits before it
+        let mut low = 0;
+        let mut high = self.superblock_ranks.capacity();
+        while high - low > 1 {
+            let middle = (low + high) / 2;
+            if Self::counted_before(middle << SUPERBLOCK_SHIFT, self.superblock_ranks[middle], ones) <= rank {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        // Then the last word inside of it, at most 8 words are scanned
+        let superblock_rank = self.superblock_ranks[low];
+        let first_word = low << SUPERBLOCK_SHIFT;
+        let last_word = (first_word + SUPERBLOCK_WORDS).min(self.block_ranks.capacity());
+        let mut word = first_word;
+        for candidate in first_word + 1..last_word {
+            let ones_before = superblock_rank + self.block_ranks[candidate] as usize;
+            if Self::counted_before(candidate, ones_before, ones) > rank {
+                break;
+            }
+            word = candidate;
+        }
+
+        let ones_before = superblock_rank + self.block_ranks[word] as usize;
+        let remaining = rank - Self::counted_before(word, ones_before, ones);
+        let mut bits = unsafe { *self.bitmap.data.add(word) };
+        if !ones {
+            bits = !bits;
+        }
+        for _ in 0..remaining {
+            // Clear the lowest set bit
+            bits &= bits - 1;
+        }
+        (word << DIV_SHIFT) + bits.trailing_zeros() as usize
+    }
+}
@@ -0,0 +1,171 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::data_structs::bitmap::atomic_bitmap::{AtomicBitmap, Mode};
+use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, BIT_MASK, DIV_SHIFT};
+
+const WORD_BITS: usize = BIT_END_OFFSET + 1;
+/// Odd multiplier that scatters consecutive thread seeds over the words
+const SEED_SPREAD: usize = 0x9E37_79B9_7F4A_7C15_u64 as usize;
+
+static NEXT_ALLOCATOR_ID: AtomicUsize = AtomicUsize::new(0);
+static NEXT_THREAD_SEED: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Distinct for every thread so threads start their scans on different words
+    static THREAD_SEED: usize = NEXT_THREAD_SEED.fetch_add(1, Ordering::Relaxed);
+    /// Allocator id and word the current thread claimed from last
+    static START_HINT: Cell<(usize, usize)> = const { Cell::new((usize::MAX, 0)) };
+}
+
+/// Lock-free allocator of slot indices. A set bit marks an acquired slot, slots are claimed
+/// with a compare exchange on their word so two threads can never get the same index.
+pub struct AtomicSlotAllocator {
+    bitmap: AtomicBitmap,
+    /// Keys the thread's start hint so it is never applied to another allocator
+    id: usize,
+}
+
+impl AtomicSlotAllocator {
+    pub fn new(slot_count: usize) -> Self {
+        AtomicSlotAllocator {
+            bitmap: AtomicBitmap::new(slot_count),
+            id: NEXT_ALLOCATOR_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.bitmap.bit_capacity()
+    }
+    #[inline(always)]
+    pub fn bitmap(&self) -> &AtomicBitmap {
+        &self.bitmap
+    }
+    #[inline(always)]
+    pub fn is_acquired(&self, slot: usize) -> bool {
+        self.bitmap.get(slot, Mode::Strict).expect("Slot out of bounds")
+    }
+
+    /// Claims a free slot, scanning word by word from the thread's hint and wrapping around
+    pub fn acquire(&self) -> Option<usize> {
+        let words = self.bitmap.capacity();
+        let start = self.start_word(words);
+        for i in 0..words {
+            let offset = (start + i) % words;
+            let valid = self.valid_mask(offset);
+            let word = unsafe { self.bitmap.word_unchecked(offset) };
+            let mut current = word.load(Ordering::Acquire);
+            loop {
+                let free = !current & valid;
+                if free == 0 {
+                    break;
+                }
+                let bit = 1 << free.trailing_zeros();
+                match word.compare_exchange_weak(current, current | bit, Ordering::AcqRel, Ordering::Acquire) {
+                    Ok(_) => {
+                        START_HINT.with(|hint| hint.set((self.id, offset)));
+                        return Some((offset << DIV_SHIFT) + free.trailing_zeros() as usize);
+                    }
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+        None
+    }
+
+    /// Claims `slot_count` adjacent slots and returns the first one. First fit, a range that
+    /// gets taken while it is being claimed is rolled back and the scan moves on.
+    pub fn acquire_n_contiguous(&self, slot_count: usize) -> Option<usize> {
+        if slot_count == 0 {
+            panic!("Slot count cannot be zero");
+        }
+        let capacity = self.capacity();
+        let mut position = 0;
+        while position + slot_count <= capacity {
+            let start = self.bitmap.first_zero(position, Mode::Strict)?;
+            if start + slot_count > capacity {
+                return None;
+            }
+            if let Some(acquired) = self.bitmap.first_one_bounds(start, start + slot_count, Mode::Strict) {
+                position = acquired + 1;
+                continue;
+            }
+            if self.try_claim(start, start + slot_count) {
+                return Some(start);
+            }
+            position = start + 1;
+        }
+        None
+    }
+
+    pub fn release(&self, slot: usize) {
+        if !self.bitmap.fetch_clear(slot, Mode::Strict) {
+            panic!("Slot was not acquired");
+        }
+    }
+
+    /// Releases every slot of a range returned by `acquire_n_contiguous`. The whole range is
+    /// checked first, a range with a free slot panics without releasing anything.
+    pub fn release_n_contiguous(&self, slot: usize, slot_count: usize) {
+        if slot + slot_count > self.capacity() {
+            panic!("Slot out of bounds");
+        }
+        if self.bitmap.count_ones(slot, slot + slot_count, Mode::Strict) != slot_count {
+            panic!("Slot was not acquired");
+        }
+        for offset in slot >> DIV_SHIFT..(slot + slot_count).div_ceil(WORD_BITS) {
+            let mask = AtomicBitmap::range_mask(offset, slot, slot + slot_count);
+            unsafe { self.bitmap.word_unchecked(offset) }.fetch_and(!mask, Ordering::AcqRel);
+        }
+    }
+
+    /// The word this thread last claimed from, or a per-thread word if it has not used this allocator
+    #[inline(always)]
+    fn start_word(&self, words: usize) -> usize {
+        match START_HINT.with(|hint| hint.get()) {
+            (id, offset) if id == self.id => offset % words,
+            _ => THREAD_SEED.with(|seed| seed.wrapping_mul(SEED_SPREAD)) % words,
+        }
+    }
+
+    /// Bits of the word that map to slots, the last word is only partially used
+    #[inline(always)]
+    fn valid_mask(&self, offset: usize) -> usize {
+        let first = offset << DIV_SHIFT;
+        let capacity = self.capacity();
+        if first + WORD_BITS <= capacity {
+            BIT_MASK
+        } else if first >= capacity {
+            0
+        } else {
+            BIT_MASK >> (WORD_BITS - (capacity - first))
+        }
+    }
+
+    /// Claims every slot in the range or none of them
+    fn try_claim(&self, lower_bound: usize, upper_bound: usize) -> bool {
+        let first_word = lower_bound >> DIV_SHIFT;
+        let last_word = (upper_bound - 1) >> DIV_SHIFT;
+        for offset in first_word..=last_word {
+            let mask = AtomicBitmap::range_mask(offset, lower_bound, upper_bound);
+            let word = unsafe { self.bitmap.word_unchecked(offset) };
+            let mut current = word.load(Ordering::Acquire);
+            loop {
+                if current & mask != 0 {
+                    // The words before this one are fully ours, give them back
+                    for claimed in first_word..offset {
+                        let mask = AtomicBitmap::range_mask(claimed, lower_bound, upper_bound);
+                        unsafe { self.bitmap.word_unchecked(claimed) }.fetch_and(!mask, Ordering::AcqRel);
+                    }
+                    return false;
+                }
+                match word.compare_exchange_weak(current, current | mask, Ordering::AcqRel, Ordering::Acquire) {
+                    Ok(_) => break,
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+        true
+    }
+}
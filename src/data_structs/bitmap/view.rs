@@ -0,0 +1,243 @@
+use std::mem::size_of;
+use std::ops::{Bound, RangeBounds};
+
+use crate::data_structs::binary::{cast_slice, cast_slice_mut, parse_header, ViewError};
+use crate::data_structs::bitmap::atomic_bitmap::AtomicBitmap;
+use crate::data_structs::bitmap::binary::TAG;
+use crate::data_structs::bitmap::consts::{BIT_END_OFFSET, BIT_MASK, DIV_SHIFT};
+use crate::data_structs::bitmap::iterator::BitIterator;
+
+const WORD_BITS: usize = BIT_END_OFFSET + 1;
+
+/// Words needed for `bit_capacity` bits, a view has no spare word past the last used one
+#[inline(always)]
+fn word_count(bit_capacity: usize) -> usize {
+    bit_capacity.div_ceil(WORD_BITS)
+}
+
+/// Native words of the first `bit_capacity` bits of the buffer, the buffer may be longer
+#[inline(always)]
+fn byte_len(bytes: usize, bit_capacity: usize) -> Result<usize, ViewError> {
+    let len = word_count(bit_capacity) * size_of::<usize>();
+    if len > bytes {
+        return Err(ViewError::InvalidLength);
+    }
+    Ok(len)
+}
+
+/// Start of the bits and the bit capacity of a buffer written by `Bitmap::write_to`
+#[inline(always)]
+fn written_bits(bytes: &[u8]) -> Result<(usize, usize), ViewError> {
+    let ([bit_capacity], start) = parse_header(bytes, TAG)?;
+    let bit_capacity = usize::try_from(bit_capacity).map_err(|_| ViewError::InvalidHeader)?;
+    Ok((start, bit_capacity))
+}
+
+/// Queries shared by both views. Bits of the last word past `bit_capacity` may hold anything
+/// that was in the buffer, so every word is masked to the queried range.
+macro_rules! impl_view_queries {
+    ($view:ident) => {
+        impl $view<'_> {
+            #[inline(always)]
+            pub fn bit_capacity(&self) -> usize {
+                self.bit_capacity
+            }
+            /// Number of words
+            #[inline(always)]
+            pub fn capacity(&self) -> usize {
+                self.words.len()
+            }
+
+            #[inline(always)]
+            pub fn get(&self, bit_index: usize) -> Option<bool> {
+                if bit_index >= self.bit_capacity {
+                    return None;
+                }
+                Some(self.words[bit_index >> DIV_SHIFT] & (1 << (bit_index & BIT_END_OFFSET)) != 0)
+            }
+
+            /// Counts the set bits in `lower_bound..upper_bound`
+            pub fn count_ones(&self, lower_bound: usize, upper_bound: usize) -> usize {
+                self.check_range(lower_bound, upper_bound);
+                if lower_bound == upper_bound {
+                    return 0;
+                }
+                let mut counter = 0;
+                for offset in lower_bound >> DIV_SHIFT..=(upper_bound - 1) >> DIV_SHIFT {
+                    let mask = AtomicBitmap::range_mask(offset, lower_bound, upper_bound);
+                    counter += (self.words[offset] & mask).count_ones() as usize;
+                }
+                counter
+            }
+            #[inline(always)]
+            pub fn count_zeros(&self, lower_bound: usize, upper_bound: usize) -> usize {
+                upper_bound - lower_bound - self.count_ones(lower_bound, upper_bound)
+            }
+
+            #[inline(always)]
+            pub fn first_one(&self, bit_index: usize) -> Option<usize> {
+                self.check_index(bit_index);
+                self.find(bit_index, self.bit_capacity, 0)
+            }
+            #[inline(always)]
+            pub fn first_zero(&self, bit_index: usize) -> Option<usize> {
+                self.check_index(bit_index);
+                self.find(bit_index, self.bit_capacity, BIT_MASK)
+            }
+            #[inline(always)]
+            pub fn first_one_bounds(&self, lower_bound: usize, upper_bound: usize) -> Option<usize> {
+                self.check_range(lower_bound, upper_bound);
+                self.find(lower_bound, upper_bound, 0)
+            }
+            #[inline(always)]
+            pub fn first_zero_bounds(&self, lower_bound: usize, upper_bound: usize) -> Option<usize> {
+                self.check_range(lower_bound, upper_bound);
+                self.find(lower_bound, upper_bound, BIT_MASK)
+            }
+
+            #[inline(always)]
+            pub fn iter_ones(&self) -> BitIterator<'_> {
+                unsafe { BitIterator::new(self.words.as_ptr(), 0, self.bit_capacity, 0) }
+            }
+            #[inline(always)]
+            pub fn iter_zeros(&self) -> BitIterator<'_> {
+                unsafe { BitIterator::new(self.words.as_ptr(), 0, self.bit_capacity, BIT_MASK) }
+            }
+            pub fn to_indices_true(&self) -> Vec<usize> {
+                self.iter_ones().collect()
+            }
+            pub fn to_indices_false(&self) -> Vec<usize> {
+                self.iter_zeros().collect()
+            }
+
+            fn find(&self, lower_bound: usize, upper_bound: usize, flip: usize) -> Option<usize> {
+                if lower_bound >= upper_bound {
+                    return None;
+                }
+                for offset in lower_bound >> DIV_SHIFT..=(upper_bound - 1) >> DIV_SHIFT {
+                    let data = (self.words[offset] ^ flip) & AtomicBitmap::range_mask(offset, lower_bound, upper_bound);
+                    if data != 0 {
+                        return Some((offset << DIV_SHIFT) + data.trailing_zeros() as usize);
+                    }
+                }
+                None
+            }
+
+            #[inline(always)]
+            fn check_index(&self, bit_index: usize) {
+                if bit_index >= self.bit_capacity {
+                    panic!("Bit index out of bounds");
+                }
+            }
+            #[inline(always)]
+            fn check_range(&self, lower_bound: usize, upper_bound: usize) {
+                if lower_bound > upper_bound {
+                    panic!("Lower bound cannot be greater than upper bound");
+                }
+                if upper_bound > self.bit_capacity {
+                    panic!("Upper bound out of bounds");
+                }
+            }
+        }
+    };
+}
+
+/// Read-only bitmap over a borrowed byte buffer, for example a memory mapped file. The buffer holds
+/// native-endian words with bit `i` at bit `i % usize::BITS` of word `i / usize::BITS`.
+#[derive(Clone, Copy)]
+pub struct BitmapView<'a> {
+    words: &'a [usize],
+    bit_capacity: usize,
+}
+
+impl<'a> BitmapView<'a> {
+    /// Views the first `bit_capacity` bits, the buffer has to be word aligned and long enough
+    pub fn from_bytes(bytes: &'a [u8], bit_capacity: usize) -> Result<Self, ViewError> {
+        let len = byte_len(bytes.len(), bit_capacity)?;
+        Ok(BitmapView {
+            words: cast_slice(&bytes[..len])?,
+            bit_capacity,
+        })
+    }
+    /// Views the bits of a buffer written by `Bitmap::write_to`. The buffer has to start at an
+    /// alignment of 16 and the target has to be little-endian, the written bits are little-endian words.
+    pub fn from_written(bytes: &'a [u8]) -> Result<Self, ViewError> {
+        let (start, bit_capacity) = written_bits(bytes)?;
+        Self::from_bytes(&bytes[start..], bit_capacity)
+    }
+}
+
+impl_view_queries!(BitmapView);
+
+/// Mutable bitmap over a borrowed byte buffer, writes go straight to the buffer
+pub struct BitmapViewMut<'a> {
+    words: &'a mut [usize],
+    bit_capacity: usize,
+}
+
+impl<'a> BitmapViewMut<'a> {
+    pub fn from_bytes(bytes: &'a mut [u8], bit_capacity: usize) -> Result<Self, ViewError> {
+        let len = byte_len(bytes.len(), bit_capacity)?;
+        Ok(BitmapViewMut {
+            words: cast_slice_mut(&mut bytes[..len])?,
+            bit_capacity,
+        })
+    }
+    /// Same as `BitmapView::from_written`, writes go straight to the written bits
+    pub fn from_written(bytes: &'a mut [u8]) -> Result<Self, ViewError> {
+        let (start, bit_capacity) = written_bits(bytes)?;
+        Self::from_bytes(&mut bytes[start..], bit_capacity)
+    }
+
+    #[inline(always)]
+    pub fn as_view(&self) -> BitmapView<'_> {
+        BitmapView {
+            words: self.words,
+            bit_capacity: self.bit_capacity,
+        }
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, bit_index: usize, value: bool) {
+        self.check_index(bit_index);
+        let word = &mut self.words[bit_index >> DIV_SHIFT];
+        let mask = 1 << (bit_index & BIT_END_OFFSET);
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+    #[inline(always)]
+    pub fn flip(&mut self, bit_index: usize) {
+        self.check_index(bit_index);
+        self.words[bit_index >> DIV_SHIFT] ^= 1 << (bit_index & BIT_END_OFFSET);
+    }
+
+    pub fn set_range(&mut self, range: impl RangeBounds<usize>, value: bool) {
+        let lower_bound = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => *start + 1,
+            Bound::Unbounded => 0,
+        };
+        let upper_bound = match range.end_bound() {
+            Bound::Included(end) => *end + 1,
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.bit_capacity,
+        };
+        self.check_range(lower_bound, upper_bound);
+        if lower_bound == upper_bound {
+            return;
+        }
+        for offset in lower_bound >> DIV_SHIFT..=(upper_bound - 1) >> DIV_SHIFT {
+            let mask = AtomicBitmap::range_mask(offset, lower_bound, upper_bound);
+            if value {
+                self.words[offset] |= mask;
+            } else {
+                self.words[offset] &= !mask;
+            }
+        }
+    }
+}
+
+impl_view_queries!(BitmapViewMut);
@@ -1,7 +1,15 @@
 pub mod array;
+pub mod binary;
 pub mod bitmap;
 pub mod queue;
+pub mod raw_alloc;
+pub mod ring_buffer;
 pub mod stack;
 
 pub mod fat_ptr;
+pub mod mpmc_queue;
 pub mod split_buffer;
+pub mod spsc_queue;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
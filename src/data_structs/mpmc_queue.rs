@@ -0,0 +1,176 @@
+use crate::utils::{closest_pow2, CachePadded};
+use std::alloc::{dealloc, Layout};
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const SPIN_LIMIT: u32 = 64;
+
+struct Slot<T> {
+    sequence: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Bounded multi producer multi consumer queue. Every slot carries a sequence number
+/// that tells producers and consumers whose turn it is (Vyukov style).
+pub struct MpmcQueue<T>
+where
+    T: Copy + Sized,
+{
+    capacity: usize,
+    layout: Layout,
+    data: *mut Slot<T>,
+    enqueue_pos: CachePadded<AtomicUsize>,
+    dequeue_pos: CachePadded<AtomicUsize>,
+}
+
+impl<T> MpmcQueue<T>
+where
+    T: Copy + Sized,
+{
+    /// The capacity is rounded up to a power of 2, with a minimum of 2.
+    pub fn new_pow2_sized(capacity: usize) -> Self {
+        let capacity = closest_pow2(capacity.max(2));
+        let layout = Layout::array::<Slot<T>>(capacity).expect("Failed to create layout");
+        let data = unsafe { std::alloc::alloc(layout) as *mut Slot<T> };
+        if data.is_null() {
+            panic!("Failed to allocate memory");
+        }
+        for i in 0..capacity {
+            unsafe {
+                data.add(i).write(Slot {
+                    sequence: AtomicUsize::new(i),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                });
+            }
+        }
+
+        MpmcQueue {
+            capacity,
+            layout,
+            data,
+            enqueue_pos: CachePadded(AtomicUsize::new(0)),
+            dequeue_pos: CachePadded(AtomicUsize::new(0)),
+        }
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Only a snapshot, other threads may change it right after.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        let dequeue_pos = self.dequeue_pos.load(Ordering::Relaxed);
+        let enqueue_pos = self.enqueue_pos.load(Ordering::Relaxed);
+        enqueue_pos.wrapping_sub(dequeue_pos).min(self.capacity)
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn try_push(&self, value: T) -> Result<(), T> {
+        let mask = self.capacity - 1;
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = unsafe { &*self.data.add(pos & mask) };
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            let diff = sequence.wrapping_sub(pos) as isize;
+            if diff == 0 {
+                match self
+                    .enqueue_pos
+                    .compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed, Ordering::Relaxed)
+                {
+                    Ok(_) => {
+                        unsafe { (*slot.value.get()).write(value) };
+                        slot.sequence.store(pos.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // The slot still holds a value from the previous lap
+                return Err(value);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn try_pop(&self) -> Option<T> {
+        let mask = self.capacity - 1;
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = unsafe { &*self.data.add(pos & mask) };
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            let diff = sequence.wrapping_sub(pos.wrapping_add(1)) as isize;
+            if diff == 0 {
+                match self
+                    .dequeue_pos
+                    .compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed, Ordering::Relaxed)
+                {
+                    Ok(_) => {
+                        let value = unsafe { (*slot.value.get()).assume_init_read() };
+                        slot.sequence.store(pos.wrapping_add(self.capacity), Ordering::Release);
+                        return Some(value);
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // The slot was not written yet
+                return None;
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Spins until there is a free slot.
+    pub fn push(&self, mut value: T) {
+        let mut spins = 0;
+        loop {
+            match self.try_push(value) {
+                Ok(()) => return,
+                Err(rejected) => value = rejected,
+            }
+            backoff(&mut spins);
+        }
+    }
+
+    /// Spins until there is a value to pop.
+    pub fn pop(&self) -> T {
+        let mut spins = 0;
+        loop {
+            if let Some(value) = self.try_pop() {
+                return value;
+            }
+            backoff(&mut spins);
+        }
+    }
+}
+
+#[inline(always)]
+fn backoff(spins: &mut u32) {
+    if *spins < SPIN_LIMIT {
+        *spins += 1;
+        std::hint::spin_loop();
+    } else {
+        std::thread::yield_now();
+    }
+}
+
+impl<T> Drop for MpmcQueue<T>
+where
+    T: Copy + Sized,
+{
+    fn drop(&mut self) {
+        unsafe {
+            dealloc(self.data as *mut u8, self.layout);
+        }
+    }
+}
+
+unsafe impl<T> Send for MpmcQueue<T> where T: Copy + Sized + Send {}
+
+unsafe impl<T> Sync for MpmcQueue<T> where T: Copy + Sized + Send {}
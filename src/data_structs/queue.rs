@@ -1,10 +1,27 @@
-use crate::utils::{closest_pow2, rotate_inc};
-use std::alloc::{dealloc, realloc, Layout};
+use crate::data_structs::binary::{
+    as_bytes, check_element_size, invalid_data, payload_len, read_header, read_payload, to_usize, write_header, write_padding, ByteOrder,
+    Pod,
+};
+use crate::data_structs::raw_alloc::{Global, RawAlloc};
+use crate::utils::{closest_pow2, rotate_dec, rotate_inc};
+use std::alloc::Layout;
+use std::fmt::Debug;
+use std::io;
+use std::io::{Read, Write};
+use std::iter::Chain;
+use std::mem::{size_of, size_of_val};
+use std::ops::{Index, IndexMut};
 use std::ptr;
+use std::slice;
 
-pub struct Queue<T>
+/// Walks the queue from front to back
+pub type QueueIterator<'a, T> = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+pub type QueueIteratorMut<'a, T> = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+pub struct Queue<T, A = Global>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     capacity: usize,
     len: usize,
@@ -12,6 +29,8 @@ where
     data: *mut T,
     front: usize,
     end: usize,
+    growable: bool,
+    alloc: A,
 }
 
 impl<T> Queue<T>
@@ -19,21 +38,66 @@ where
     T: Copy + Sized,
 {
     pub fn new_pow2_sized(capacity: usize) -> Self {
-        let capacity = closest_pow2(capacity);
-        let layout = Layout::array::<T>(capacity).expect("Failed to create layout");
-        let data = unsafe { std::alloc::alloc(layout) as *mut T };
+        Self::new_pow2_sized_in(capacity, Global)
+    }
+    /// Creates a queue that doubles its capacity instead of panicking when it is full.
+    pub fn new_pow2_sized_growable(capacity: usize) -> Self {
+        Self::new_pow2_sized_growable_in(capacity, Global)
+    }
+    #[inline(always)]
+    pub fn try_new_pow2_sized(capacity: usize) -> Option<Self> {
+        Self::try_new_pow2_sized_in(capacity, Global)
+    }
+    pub fn try_new_pow2_sized_growable(capacity: usize) -> Option<Self> {
+        let mut queue = Self::try_new_pow2_sized(capacity)?;
+        queue.growable = true;
+        Some(queue)
+    }
+}
+
+impl<T, A> Queue<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    pub fn new_pow2_sized_in(capacity: usize, alloc: A) -> Self {
+        Self::try_new_pow2_sized_in(capacity, alloc).expect("Failed to allocate memory")
+    }
+    /// Same as `new_pow2_sized_in` but returns None instead of panicking when the capacity or
+    /// layout overflows or the allocation fails
+    pub fn try_new_pow2_sized_in(capacity: usize, alloc: A) -> Option<Self> {
+        let capacity = capacity.checked_next_power_of_two()?;
+        let layout = Layout::array::<T>(capacity).ok()?;
+        let data = unsafe { alloc.alloc(layout) as *mut T };
         if data.is_null() {
-            panic!("Failed to allocate memory");
+            return None;
         }
 
-        Queue {
+        Some(Queue {
             capacity,
             layout,
             data,
             len: 0,
             front: 0,
             end: 0,
-        }
+            growable: false,
+            alloc,
+        })
+    }
+    pub fn new_pow2_sized_growable_in(capacity: usize, alloc: A) -> Self {
+        let mut queue = Self::new_pow2_sized_in(capacity, alloc);
+        queue.growable = true;
+        queue
+    }
+
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    #[inline(always)]
+    pub fn is_growable(&self) -> bool {
+        self.growable
     }
 
     #[inline(always)]
@@ -50,7 +114,7 @@ where
         let new_layout = Layout::array::<T>(new_capacity).expect("Failed to create layout");
         // Data can only wrap if data actually exists. We need to do len check.
         if self.front >= self.end && self.len > 0 {
-            let new_data = unsafe { std::alloc::alloc(new_layout) as *mut T };
+            let new_data = unsafe { self.alloc.alloc(new_layout) as *mut T };
             if new_data.is_null() {
                 panic!("Failed to allocate memory");
             }
@@ -71,8 +135,9 @@ where
                 )
             }; // Before Front
 
-            unsafe { dealloc(self.data as *mut u8, self.layout) };
+            unsafe { self.alloc.dealloc(self.data as *mut u8, self.layout) };
             self.capacity = new_capacity;
+            self.layout = new_layout;
             self.data = new_data;
             self.front = 0;
             self.end = from_front_to_array_end_len + from_start_to_end_len;
@@ -80,16 +145,17 @@ where
         }
 
         unsafe {
-            self.data = realloc(self.data as *mut u8, new_layout, new_layout.size()) as *mut T;
+            self.data = self.alloc.realloc(self.data as *mut u8, self.layout, new_layout.size()) as *mut T;
             if self.data.is_null() {
                 panic!("Failed to reallocate memory");
             }
             self.capacity = new_capacity;
+            self.layout = new_layout;
         }
     }
     #[inline(always)]
     pub fn extend_pow2_sized_by(&mut self, capacity_pow: usize) {
-        if self.capacity < self.capacity + capacity_pow {
+        if capacity_pow == 0 {
             return;
         }
         let new_capacity = closest_pow2(self.capacity + capacity_pow);
@@ -104,10 +170,18 @@ where
         self.len
     }
 
+    #[cold]
+    fn grow(&mut self) {
+        if !self.growable {
+            panic!("Queue is full");
+        }
+        self.extend_pow2_sized(self.capacity * 2);
+    }
+
     #[inline(always)]
     pub fn push(&mut self, value: T) {
         if self.len == self.capacity {
-            panic!("Queue is full");
+            self.grow();
         }
         unsafe {
             self.data.add(self.end).write(value);
@@ -115,6 +189,35 @@ where
             self.end = rotate_inc(self.end, self.capacity - 1);
         }
     }
+    /// Never grows. When the queue is full the front element is overwritten and returned.
+    #[inline(always)]
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        if self.len != self.capacity {
+            unsafe { self.data.add(self.end).write(value) };
+            self.len += 1;
+            self.end = rotate_inc(self.end, self.capacity - 1);
+            return None;
+        }
+        unsafe {
+            // When full the end and front point to the same slot
+            let oldest = self.data.add(self.front).read();
+            self.data.add(self.end).write(value);
+            self.front = rotate_inc(self.front, self.capacity - 1);
+            self.end = self.front;
+            Some(oldest)
+        }
+    }
+    #[inline(always)]
+    pub fn push_front(&mut self, value: T) {
+        if self.len == self.capacity {
+            self.grow();
+        }
+        unsafe {
+            self.front = rotate_dec(self.front, self.capacity - 1);
+            self.data.add(self.front).write(value);
+            self.len += 1;
+        }
+    }
     #[inline(always)]
     pub fn front(&self) -> Option<&T> {
         if self.len == 0 {
@@ -141,15 +244,309 @@ where
             result
         }
     }
+    #[inline(always)]
+    pub fn back(&self) -> Option<&T> {
+        if self.len == 0 {
+            return None;
+        }
+        unsafe { Some(self.data.add(rotate_dec(self.end, self.capacity - 1)).as_ref().unwrap()) }
+    }
+    #[inline(always)]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        unsafe { Some(self.data.add(rotate_dec(self.end, self.capacity - 1)).as_mut().unwrap()) }
+    }
+    #[inline(always)]
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        unsafe {
+            self.end = rotate_dec(self.end, self.capacity - 1);
+            self.len -= 1;
+            Some(self.data.add(self.end).read())
+        }
+    }
+
+    /// Returns the elements from front to back as two segments. The second one is only
+    /// non empty when the data wraps around the end of the ring.
+    #[inline(always)]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        if self.len == 0 {
+            return (&[], &[]);
+        }
+        unsafe {
+            if self.front < self.end {
+                return (slice::from_raw_parts(self.data.add(self.front), self.len), &[]);
+            }
+            (
+                slice::from_raw_parts(self.data.add(self.front), self.capacity - self.front),
+                slice::from_raw_parts(self.data, self.end),
+            )
+        }
+    }
+    #[inline(always)]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        if self.len == 0 {
+            return (&mut [], &mut []);
+        }
+        unsafe {
+            if self.front < self.end {
+                return (slice::from_raw_parts_mut(self.data.add(self.front), self.len), &mut []);
+            }
+            (
+                slice::from_raw_parts_mut(self.data.add(self.front), self.capacity - self.front),
+                slice::from_raw_parts_mut(self.data, self.end),
+            )
+        }
+    }
+    #[inline(always)]
+    pub fn iter(&self) -> QueueIterator<'_, T> {
+        let (head, tail) = self.as_slices();
+        head.iter().chain(tail.iter())
+    }
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> QueueIteratorMut<'_, T> {
+        let (head, tail) = self.as_mut_slices();
+        head.iter_mut().chain(tail.iter_mut())
+    }
+    /// Dequeues the elements while iterating. Whatever is not consumed is dropped with the iterator.
+    #[inline(always)]
+    pub fn drain(&mut self) -> QueueDrain<'_, T, A> {
+        QueueDrain { queue: self }
+    }
+    /// Index 0 is the front of the queue
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        unsafe { Some(self.index_unchecked(index)) }
+    }
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        unsafe { Some(self.index_unchecked_mut(index)) }
+    }
+    /// # Safety
+    /// `index` must be less than `len()`.
+    #[inline(always)]
+    pub unsafe fn index_unchecked(&self, index: usize) -> &T {
+        self.data.add((self.front + index) & (self.capacity - 1)).as_ref().unwrap()
+    }
+    /// # Safety
+    /// `index` must be less than `len()`.
+    #[inline(always)]
+    pub unsafe fn index_unchecked_mut(&mut self, index: usize) -> &mut T {
+        self.data.add((self.front + index) & (self.capacity - 1)).as_mut().unwrap()
+    }
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.front = 0;
+        self.end = 0;
+    }
+}
+
+const TAG: &[u8; 4] = b"ETQU";
+
+/// Header with the element size, capacity, growable flag and length, then the elements
+/// from front to back as raw native-endian bytes. A read queue starts at the front of its buffer.
+/// Reading on a target with the other byte order fails with `InvalidData`.
+impl<T> Queue<T>
+where
+    T: Pod,
+{
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let header = [size_of::<T>() as u64, self.capacity as u64, self.growable as u64, self.len as u64];
+        write_header(writer, TAG, ByteOrder::Native, &header)?;
+        let (front, back) = self.as_slices();
+        writer.write_all(as_bytes(front))?;
+        writer.write_all(as_bytes(back))?;
+        write_padding(writer, size_of_val(front) + size_of_val(back))
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let [element_size, capacity, growable, len] = read_header(reader, TAG, ByteOrder::Native)?;
+        check_element_size::<T>(element_size)?;
+        let (capacity, len) = (to_usize(capacity)?, to_usize(len)?);
+        if !capacity.is_power_of_two() || len > capacity || growable > 1 {
+            return Err(invalid_data("Invalid queue header"));
+        }
+        let bytes = read_payload(reader, payload_len::<T>(len)?)?;
+        let mut queue = Self::try_new_pow2_sized(capacity).ok_or_else(|| invalid_data("Capacity too large"))?;
+        queue.growable = growable == 1;
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), queue.data as *mut u8, bytes.len()) };
+        queue.len = len;
+        queue.end = len & (capacity - 1);
+        Ok(queue)
+    }
+}
+
+pub struct QueueDrain<'a, T, A = Global>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    queue: &'a mut Queue<T, A>,
+}
+
+impl<T, A> Iterator for QueueDrain<'_, T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    type Item = T;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.dequeue()
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len, Some(self.queue.len))
+    }
+}
+
+impl<T, A> DoubleEndedIterator for QueueDrain<'_, T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.queue.pop_back()
+    }
+}
+
+impl<T, A> ExactSizeIterator for QueueDrain<'_, T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+}
+
+impl<T, A> Drop for QueueDrain<'_, T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    fn drop(&mut self) {
+        self.queue.clear();
+    }
+}
+
+impl<T, A> Clone for Queue<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc + Clone,
+{
+    fn clone(&self) -> Self {
+        let mut queue = Self::new_pow2_sized_in(self.capacity, self.alloc.clone());
+        queue.growable = self.growable;
+        let (head, tail) = self.as_slices();
+        unsafe {
+            ptr::copy_nonoverlapping(head.as_ptr(), queue.data, head.len());
+            ptr::copy_nonoverlapping(tail.as_ptr(), queue.data.add(head.len()), tail.len());
+        }
+        queue.len = self.len;
+        queue.end = self.len & (self.capacity - 1);
+        queue
+    }
+}
+
+impl<T, A> Debug for Queue<T, A>
+where
+    T: Copy + Sized + Debug,
+    A: RawAlloc,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Queue [len={}, cap={}] [", self.len, self.capacity)?;
+        for value in self.iter() {
+            write!(f, " {:?}", value)?;
+        }
+        write!(f, " ]")?;
+        Ok(())
+    }
+}
+
+impl<T, A> Extend<T> for Queue<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// The collected queue is growable since the iterator length is not known up front.
+impl<T> FromIterator<T> for Queue<T>
+where
+    T: Copy + Sized,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut queue = Self::new_pow2_sized_growable(iter.size_hint().0.max(1));
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<'a, T, A> IntoIterator for &'a Queue<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    type Item = &'a T;
+    type IntoIter = QueueIterator<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, A> Index<usize> for Queue<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    type Output = T;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.len {
+            panic!("Index out of bounds");
+        }
+        unsafe { self.index_unchecked(index) }
+    }
+}
+
+impl<T, A> IndexMut<usize> for Queue<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        if index >= self.len {
+            panic!("Index out of bounds");
+        }
+        unsafe { self.index_unchecked_mut(index) }
+    }
 }
 
-impl<T> Drop for Queue<T>
+impl<T, A> Drop for Queue<T, A>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     fn drop(&mut self) {
         unsafe {
-            dealloc(self.data as *mut u8, self.layout);
+            self.alloc.dealloc(self.data as *mut u8, self.layout);
         }
     }
 }
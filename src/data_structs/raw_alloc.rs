@@ -0,0 +1,76 @@
+use std::alloc::{self, Layout};
+use std::ptr::NonNull;
+
+/// Source of raw memory for the data structures. The methods follow `GlobalAlloc`, except that
+/// a zero sized layout is allowed everywhere.
+///
+/// # Safety
+/// A non-null block returned by `alloc` or `realloc` has to fit the requested layout and stay valid
+/// until it is passed back to `realloc` or `dealloc`. Null means the allocation failed.
+pub unsafe trait RawAlloc {
+    /// # Safety
+    /// The block is uninitialized and has to be released through the same allocator.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8;
+    /// # Safety
+    /// `ptr` has to come from this allocator with `layout` and not be released yet. `new_size` rounded
+    /// up to the alignment must not overflow `isize`. On success the old pointer is no longer valid.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8;
+    /// # Safety
+    /// `ptr` has to come from this allocator with `layout` and not be released yet.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout);
+}
+
+/// The global allocator. Zero sized blocks never reach it, they are dangling aligned pointers.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Global;
+
+#[inline(always)]
+fn dangling(align: usize) -> *mut u8 {
+    NonNull::<u8>::dangling().as_ptr().wrapping_add(align - 1)
+}
+
+unsafe impl RawAlloc for Global {
+    #[inline(always)]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() == 0 {
+            return dangling(layout.align());
+        }
+        alloc::alloc(layout)
+    }
+    #[inline(always)]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if layout.size() == 0 {
+            return self.alloc(Layout::from_size_align_unchecked(new_size, layout.align()));
+        }
+        if new_size == 0 {
+            alloc::dealloc(ptr, layout);
+            return dangling(layout.align());
+        }
+        alloc::realloc(ptr, layout, new_size)
+    }
+    #[inline(always)]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if layout.size() != 0 {
+            alloc::dealloc(ptr, layout);
+        }
+    }
+}
+
+/// A shared allocator, for example an arena that outlives the structures it backs
+unsafe impl<A> RawAlloc for &A
+where
+    A: RawAlloc + ?Sized,
+{
+    #[inline(always)]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        (**self).alloc(layout)
+    }
+    #[inline(always)]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        (**self).realloc(ptr, layout, new_size)
+    }
+    #[inline(always)]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        (**self).dealloc(ptr, layout)
+    }
+}
@@ -0,0 +1,122 @@
+use crate::data_structs::queue::{Queue, QueueIterator};
+use std::fmt::Debug;
+use std::ops::Index;
+
+/// Fixed size history. Pushing into a full buffer overwrites the oldest element.
+pub struct RingBuffer<T>
+where
+    T: Copy + Sized,
+{
+    queue: Queue<T>,
+}
+
+impl<T> RingBuffer<T>
+where
+    T: Copy + Sized,
+{
+    pub fn new_pow2_sized(capacity: usize) -> Self {
+        RingBuffer {
+            queue: Queue::new_pow2_sized(capacity),
+        }
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.queue.len() == self.queue.capacity()
+    }
+
+    /// Returns the overwritten oldest element if the buffer was full
+    #[inline(always)]
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        self.queue.push_overwrite(value)
+    }
+    #[inline(always)]
+    pub fn latest(&self) -> Option<&T> {
+        self.queue.back()
+    }
+    #[inline(always)]
+    pub fn oldest(&self) -> Option<&T> {
+        self.queue.front()
+    }
+    #[inline(always)]
+    pub fn pop_oldest(&mut self) -> Option<T> {
+        self.queue.dequeue()
+    }
+    /// Index 0 is the oldest element
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.queue.get(index)
+    }
+    /// Iterates from the oldest to the newest element
+    #[inline(always)]
+    pub fn iter(&self) -> QueueIterator<'_, T> {
+        self.queue.iter()
+    }
+    #[inline(always)]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.queue.as_slices()
+    }
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+}
+
+impl<T> Clone for RingBuffer<T>
+where
+    T: Copy + Sized,
+{
+    fn clone(&self) -> Self {
+        RingBuffer {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<T> Debug for RingBuffer<T>
+where
+    T: Copy + Sized + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RingBuffer [len={}, cap={}] [", self.len(), self.capacity())?;
+        for value in self.iter() {
+            write!(f, " {:?}", value)?;
+        }
+        write!(f, " ]")?;
+        Ok(())
+    }
+}
+
+impl<T> Index<usize> for RingBuffer<T>
+where
+    T: Copy + Sized,
+{
+    type Output = T;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.queue[index]
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T>
+where
+    T: Copy + Sized,
+{
+    type Item = &'a T;
+    type IntoIter = QueueIterator<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
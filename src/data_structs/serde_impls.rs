@@ -0,0 +1,172 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::data_structs::array::Array;
+use crate::data_structs::bitmap::Bitmap;
+use crate::data_structs::queue::Queue;
+use crate::data_structs::stack::Stack;
+
+#[derive(Serialize)]
+#[serde(rename = "Bitmap")]
+struct BitmapRef<'a> {
+    bit_capacity: usize,
+    /// Little-endian bit stream, bit `i` is bit `i % 8` of byte `i / 8`
+    bytes: &'a [u8],
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Bitmap")]
+struct BitmapOwned {
+    bit_capacity: usize,
+    bytes: Vec<u8>,
+}
+
+impl Serialize for Bitmap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.le_bytes();
+        BitmapRef {
+            bit_capacity: self.bit_capacity(),
+            bytes: &bytes,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bitmap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let owned = BitmapOwned::deserialize(deserializer)?;
+        Bitmap::from_le_bytes(owned.bit_capacity, &owned.bytes).ok_or_else(|| D::Error::custom("Byte count does not match bit_capacity"))
+    }
+}
+
+/// Arrays are plain sequences
+impl<T> Serialize for Array<T>
+where
+    T: Copy + Sized + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Array<T>
+where
+    T: Copy + Sized + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Array::from_vec(Vec::deserialize(deserializer)?))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Stack")]
+struct StackRef<'a, T: Serialize> {
+    capacity: usize,
+    growth_factor: usize,
+    /// Bottom to top
+    items: &'a [T],
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Stack")]
+struct StackOwned<T> {
+    capacity: usize,
+    growth_factor: usize,
+    items: Vec<T>,
+}
+
+impl<T> Serialize for Stack<T>
+where
+    T: Copy + Sized + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StackRef {
+            capacity: self.capacity(),
+            growth_factor: self.growth_factor(),
+            items: self.as_slice(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Stack<T>
+where
+    T: Copy + Sized + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let owned = StackOwned::<T>::deserialize(deserializer)?;
+        if owned.items.len() > owned.capacity || owned.growth_factor == 1 {
+            return Err(D::Error::custom("Invalid stack"));
+        }
+        let stack = if owned.growth_factor == 0 {
+            Stack::try_new(owned.capacity)
+        } else {
+            Stack::try_new_growable(owned.capacity, owned.growth_factor)
+        };
+        let mut stack = stack.ok_or_else(|| D::Error::custom("Capacity too large"))?;
+        stack.push_slice(&owned.items);
+        Ok(stack)
+    }
+}
+
+/// Serializes the elements front to back without collecting them first
+struct QueueItems<'a, T: Copy + Sized>(&'a Queue<T>);
+
+impl<T> Serialize for QueueItems<'_, T>
+where
+    T: Copy + Sized + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Queue")]
+struct QueueRef<'a, T: Copy + Sized + Serialize> {
+    capacity: usize,
+    growable: bool,
+    items: QueueItems<'a, T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Queue")]
+struct QueueOwned<T> {
+    capacity: usize,
+    growable: bool,
+    items: Vec<T>,
+}
+
+impl<T> Serialize for Queue<T>
+where
+    T: Copy + Sized + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        QueueRef {
+            capacity: self.capacity(),
+            growable: self.is_growable(),
+            items: QueueItems(self),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Queue<T>
+where
+    T: Copy + Sized + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let owned = QueueOwned::<T>::deserialize(deserializer)?;
+        if !owned.capacity.is_power_of_two() || owned.items.len() > owned.capacity {
+            return Err(D::Error::custom("Invalid queue"));
+        }
+        let queue = if owned.growable {
+            Queue::try_new_pow2_sized_growable(owned.capacity)
+        } else {
+            Queue::try_new_pow2_sized(owned.capacity)
+        };
+        let mut queue = queue.ok_or_else(|| D::Error::custom("Capacity too large"))?;
+        queue.extend(owned.items);
+        Ok(queue)
+    }
+}
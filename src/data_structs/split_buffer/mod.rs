@@ -0,0 +1,305 @@
+use std::alloc::{dealloc, realloc, Layout};
+use std::fmt::Debug;
+use std::ops::{Index, IndexMut};
+use std::ptr;
+
+/// Gap buffer. The elements before the cursor live at the start of the allocation,
+/// the elements after the cursor live at the end of it and the gap sits in between.
+pub struct SplitBuffer<T>
+where
+    T: Copy + Sized,
+{
+    capacity: usize,
+    layout: Layout,
+    data: *mut T,
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl<T> SplitBuffer<T>
+where
+    T: Copy + Sized,
+{
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let layout = Layout::array::<T>(capacity).expect("Failed to create layout");
+        let data = unsafe { std::alloc::alloc(layout) as *mut T };
+        if data.is_null() {
+            panic!("Failed to allocate memory");
+        }
+
+        SplitBuffer {
+            capacity,
+            layout,
+            data,
+            gap_start: 0,
+            gap_end: capacity,
+        }
+    }
+
+    /// Copies the contents of the slice into the buffer. The cursor is placed at the end.
+    pub fn from_slice(slice: &[T]) -> Self {
+        let mut buffer = Self::new(slice.len());
+        unsafe { ptr::copy_nonoverlapping(slice.as_ptr(), buffer.data, slice.len()) };
+        buffer.gap_start = slice.len();
+        buffer
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.capacity - self.gap_len()
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.gap_start == 0 && self.gap_end == self.capacity
+    }
+    #[inline(always)]
+    pub fn gap_len(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+    /// Number of elements before the cursor
+    #[inline(always)]
+    pub fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    pub fn extend(&mut self, new_capacity: usize) {
+        if new_capacity <= self.capacity {
+            panic!("New capacity is less than or equal to current capacity");
+        }
+
+        let new_layout = Layout::array::<T>(new_capacity).expect("Failed to create layout");
+        let tail_len = self.capacity - self.gap_end;
+        unsafe {
+            self.data = realloc(self.data as *mut u8, self.layout, new_layout.size()) as *mut T;
+            if self.data.is_null() {
+                panic!("Failed to reallocate memory");
+            }
+            // The elements after the cursor have to stay at the end of the allocation
+            let new_gap_end = new_capacity - tail_len;
+            ptr::copy(self.data.add(self.gap_end), self.data.add(new_gap_end), tail_len);
+            self.gap_end = new_gap_end;
+        }
+        self.capacity = new_capacity;
+        self.layout = new_layout;
+    }
+    #[inline(always)]
+    pub fn extend_by(&mut self, additional_capacity: usize) {
+        self.extend(self.capacity + additional_capacity);
+    }
+
+    /// Moves the cursor to the given position. Costs the distance moved.
+    pub fn set_cursor(&mut self, position: usize) {
+        if position > self.len() {
+            panic!("Cursor out of bounds");
+        }
+
+        unsafe {
+            if position < self.gap_start {
+                let moved = self.gap_start - position;
+                ptr::copy(self.data.add(position), self.data.add(self.gap_end - moved), moved);
+                self.gap_start -= moved;
+                self.gap_end -= moved;
+            } else if position > self.gap_start {
+                let moved = position - self.gap_start;
+                ptr::copy(self.data.add(self.gap_end), self.data.add(self.gap_start), moved);
+                self.gap_start += moved;
+                self.gap_end += moved;
+            }
+        }
+    }
+    #[inline(always)]
+    pub fn cursor_left(&mut self) -> bool {
+        if self.gap_start == 0 {
+            return false;
+        }
+        unsafe {
+            self.gap_start -= 1;
+            self.gap_end -= 1;
+            self.data.add(self.gap_end).write(self.data.add(self.gap_start).read());
+        }
+        true
+    }
+    #[inline(always)]
+    pub fn cursor_right(&mut self) -> bool {
+        if self.gap_end == self.capacity {
+            return false;
+        }
+        unsafe {
+            self.data.add(self.gap_start).write(self.data.add(self.gap_end).read());
+            self.gap_start += 1;
+            self.gap_end += 1;
+        }
+        true
+    }
+
+    /// Inserts the value before the cursor. Grows the buffer by doubling if the gap is empty.
+    #[inline(always)]
+    pub fn insert(&mut self, value: T) {
+        if self.gap_start == self.gap_end {
+            self.extend(self.capacity * 2);
+        }
+        unsafe { self.data.add(self.gap_start).write(value) };
+        self.gap_start += 1;
+    }
+
+    pub fn insert_slice(&mut self, values: &[T]) {
+        if values.len() > self.gap_len() {
+            let required = self.len() + values.len();
+            self.extend(required.max(self.capacity * 2));
+        }
+        unsafe { ptr::copy_nonoverlapping(values.as_ptr(), self.data.add(self.gap_start), values.len()) };
+        self.gap_start += values.len();
+    }
+
+    /// Removes the element after the cursor
+    #[inline(always)]
+    pub fn delete(&mut self) -> Option<T> {
+        if self.gap_end == self.capacity {
+            return None;
+        }
+        let result = unsafe { self.data.add(self.gap_end).read() };
+        self.gap_end += 1;
+        Some(result)
+    }
+
+    /// Removes the element before the cursor
+    #[inline(always)]
+    pub fn backspace(&mut self) -> Option<T> {
+        if self.gap_start == 0 {
+            return None;
+        }
+        self.gap_start -= 1;
+        Some(unsafe { self.data.add(self.gap_start).read() })
+    }
+
+    pub fn clear(&mut self) {
+        self.gap_start = 0;
+        self.gap_end = self.capacity;
+    }
+
+    /// Returns the elements before and after the cursor
+    #[inline(always)]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        unsafe {
+            (
+                std::slice::from_raw_parts(self.data, self.gap_start),
+                std::slice::from_raw_parts(self.data.add(self.gap_end), self.capacity - self.gap_end),
+            )
+        }
+    }
+    #[inline(always)]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        unsafe {
+            (
+                std::slice::from_raw_parts_mut(self.data, self.gap_start),
+                std::slice::from_raw_parts_mut(self.data.add(self.gap_end), self.capacity - self.gap_end),
+            )
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        let (left, right) = self.as_slices();
+        let mut vec = Vec::with_capacity(self.len());
+        vec.extend_from_slice(left);
+        vec.extend_from_slice(right);
+        vec
+    }
+
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(unsafe { self.index_unchecked(index) })
+    }
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(unsafe { self.index_unchecked_mut(index) })
+    }
+    /// # Safety
+    /// `index` must be less than `len()`.
+    #[inline(always)]
+    pub unsafe fn index_unchecked(&self, index: usize) -> &T {
+        &*self.data.add(self.physical_index(index))
+    }
+    /// # Safety
+    /// `index` must be less than `len()`.
+    #[inline(always)]
+    pub unsafe fn index_unchecked_mut(&mut self, index: usize) -> &mut T {
+        &mut *self.data.add(self.physical_index(index))
+    }
+
+    #[inline(always)]
+    fn physical_index(&self, index: usize) -> usize {
+        if index < self.gap_start {
+            index
+        } else {
+            index + self.gap_len()
+        }
+    }
+}
+
+impl<T> Debug for SplitBuffer<T>
+where
+    T: Copy + Sized + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SplitBuffer [len={}, cap={}, cursor={}] [", self.len(), self.capacity, self.gap_start)?;
+        let (left, right) = self.as_slices();
+        for value in left {
+            write!(f, " {:?}", value)?;
+        }
+        write!(f, " |")?;
+        for value in right {
+            write!(f, " {:?}", value)?;
+        }
+        write!(f, " ]")?;
+        Ok(())
+    }
+}
+
+impl<T> Index<usize> for SplitBuffer<T>
+where
+    T: Copy + Sized,
+{
+    type Output = T;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.len() {
+            panic!("Index out of bounds");
+        }
+        unsafe { self.index_unchecked(index) }
+    }
+}
+
+impl<T> IndexMut<usize> for SplitBuffer<T>
+where
+    T: Copy + Sized,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        if index >= self.len() {
+            panic!("Index out of bounds");
+        }
+        unsafe { self.index_unchecked_mut(index) }
+    }
+}
+
+impl<T> Drop for SplitBuffer<T>
+where
+    T: Copy + Sized,
+{
+    fn drop(&mut self) {
+        unsafe {
+            dealloc(self.data as *mut u8, self.layout);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use crate::utils::{closest_pow2, CachePadded};
+use std::alloc::{dealloc, Layout};
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Lock-free single producer single consumer ring. Head and tail are free running counters,
+/// the slot is found by masking with `capacity - 1` just like in `Queue`.
+pub struct SpscQueue<T>
+where
+    T: Copy + Sized,
+{
+    capacity: usize,
+    layout: Layout,
+    data: *mut T,
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+}
+
+pub struct Producer<T>
+where
+    T: Copy + Sized,
+{
+    queue: Arc<SpscQueue<T>>,
+    cached_head: usize,
+    tail: usize,
+}
+
+pub struct Consumer<T>
+where
+    T: Copy + Sized,
+{
+    queue: Arc<SpscQueue<T>>,
+    cached_tail: usize,
+    head: usize,
+}
+
+impl<T> SpscQueue<T>
+where
+    T: Copy + Sized,
+{
+    pub fn new_pow2_sized(capacity: usize) -> Self {
+        let capacity = closest_pow2(capacity);
+        let layout = Layout::array::<T>(capacity).expect("Failed to create layout");
+        let data = unsafe { std::alloc::alloc(layout) as *mut T };
+        if data.is_null() {
+            panic!("Failed to allocate memory");
+        }
+
+        SpscQueue {
+            capacity,
+            layout,
+            data,
+            head: CachePadded(AtomicUsize::new(0)),
+            tail: CachePadded(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn split(self) -> (Producer<T>, Consumer<T>) {
+        let queue = Arc::new(self);
+        (
+            Producer {
+                queue: queue.clone(),
+                cached_head: 0,
+                tail: 0,
+            },
+            Consumer {
+                queue,
+                cached_tail: 0,
+                head: 0,
+            },
+        )
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies `len` elements starting at the logical `position` into `dst`, handling the wrap.
+    #[inline(always)]
+    unsafe fn read_wrapped(&self, position: usize, dst: *mut T, len: usize) {
+        let index = position & (self.capacity - 1);
+        let first = len.min(self.capacity - index);
+        ptr::copy_nonoverlapping(self.data.add(index), dst, first);
+        ptr::copy_nonoverlapping(self.data, dst.add(first), len - first);
+    }
+
+    /// Copies `len` elements from `src` to the logical `position`, handling the wrap.
+    #[inline(always)]
+    unsafe fn write_wrapped(&self, position: usize, src: *const T, len: usize) {
+        let index = position & (self.capacity - 1);
+        let first = len.min(self.capacity - index);
+        ptr::copy_nonoverlapping(src, self.data.add(index), first);
+        ptr::copy_nonoverlapping(src.add(first), self.data, len - first);
+    }
+}
+
+impl<T> Producer<T>
+where
+    T: Copy + Sized,
+{
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity
+    }
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.tail.wrapping_sub(self.queue.head.load(Ordering::Acquire))
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.queue.capacity
+    }
+
+    /// Free slots, refreshes the cached head only when the cached one says the ring is full enough.
+    #[inline(always)]
+    fn free_slots(&mut self, required: usize) -> usize {
+        let free = self.queue.capacity - self.tail.wrapping_sub(self.cached_head);
+        if free >= required {
+            return free;
+        }
+        self.cached_head = self.queue.head.load(Ordering::Acquire);
+        self.queue.capacity - self.tail.wrapping_sub(self.cached_head)
+    }
+
+    #[inline(always)]
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.free_slots(1) == 0 {
+            return Err(value);
+        }
+        unsafe {
+            self.queue.data.add(self.tail & (self.queue.capacity - 1)).write(value);
+        }
+        self.tail = self.tail.wrapping_add(1);
+        self.queue.tail.store(self.tail, Ordering::Release);
+        Ok(())
+    }
+
+    /// Pushes as many elements from the slice as fit. Returns the number of pushed elements.
+    pub fn push_slice(&mut self, values: &[T]) -> usize {
+        let len = values.len().min(self.free_slots(values.len()));
+        if len == 0 {
+            return 0;
+        }
+        unsafe { self.queue.write_wrapped(self.tail, values.as_ptr(), len) };
+        self.tail = self.tail.wrapping_add(len);
+        self.queue.tail.store(self.tail, Ordering::Release);
+        len
+    }
+}
+
+impl<T> Consumer<T>
+where
+    T: Copy + Sized,
+{
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity
+    }
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.queue.tail.load(Ordering::Acquire).wrapping_sub(self.head)
+    }
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Available elements, refreshes the cached tail only when the cached one is not enough.
+    #[inline(always)]
+    fn available(&mut self, required: usize) -> usize {
+        let available = self.cached_tail.wrapping_sub(self.head);
+        if available >= required {
+            return available;
+        }
+        self.cached_tail = self.queue.tail.load(Ordering::Acquire);
+        self.cached_tail.wrapping_sub(self.head)
+    }
+
+    #[inline(always)]
+    pub fn try_pop(&mut self) -> Option<T> {
+        if self.available(1) == 0 {
+            return None;
+        }
+        let value = unsafe { self.queue.data.add(self.head & (self.queue.capacity - 1)).read() };
+        self.head = self.head.wrapping_add(1);
+        self.queue.head.store(self.head, Ordering::Release);
+        Some(value)
+    }
+
+    /// Pops as many elements as fit into the slice. Returns the number of popped elements.
+    pub fn pop_into(&mut self, values: &mut [T]) -> usize {
+        let len = values.len().min(self.available(values.len()));
+        if len == 0 {
+            return 0;
+        }
+        unsafe { self.queue.read_wrapped(self.head, values.as_mut_ptr(), len) };
+        self.head = self.head.wrapping_add(len);
+        self.queue.head.store(self.head, Ordering::Release);
+        len
+    }
+}
+
+impl<T> Drop for SpscQueue<T>
+where
+    T: Copy + Sized,
+{
+    fn drop(&mut self) {
+        unsafe {
+            dealloc(self.data as *mut u8, self.layout);
+        }
+    }
+}
+
+unsafe impl<T> Send for SpscQueue<T> where T: Copy + Sized + Send {}
+
+unsafe impl<T> Sync for SpscQueue<T> where T: Copy + Sized + Send {}
@@ -1,11 +1,27 @@
-use std::alloc::{realloc, Layout};
+use std::alloc::Layout;
 use std::fmt::Debug;
+use std::io;
+use std::io::{Read, Write};
+use std::iter::Rev;
+use std::mem::size_of;
 use std::ops::{Index, IndexMut};
 use std::ptr;
+use std::slice;
 
-pub struct Stack<T>
+use crate::data_structs::binary::{
+    as_bytes, check_element_size, invalid_data, payload_len, read_header, read_payload, to_usize, write_header, write_padding, ByteOrder,
+    Pod,
+};
+use crate::data_structs::raw_alloc::{Global, RawAlloc};
+
+/// Walks the stack from top to bottom
+pub type StackIterator<'a, T> = Rev<slice::Iter<'a, T>>;
+pub type StackIteratorMut<'a, T> = Rev<slice::IterMut<'a, T>>;
+
+pub struct Stack<T, A = Global>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     capacity: usize,
     len: usize,
@@ -13,11 +29,35 @@ where
     data: *mut T,
     top: *mut T,
     end: *mut T,
+    growth_factor: usize,
+    alloc: A,
 }
 
 impl<T> Stack<T>
 where
     T: Copy + Sized,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self::new_in(capacity, Global)
+    }
+    /// Creates a stack that multiplies its capacity by `growth_factor` instead of panicking when it is full.
+    pub fn new_growable(capacity: usize, growth_factor: usize) -> Self {
+        Self::new_growable_in(capacity, growth_factor, Global)
+    }
+    #[inline(always)]
+    pub fn try_new(capacity: usize) -> Option<Self> {
+        Self::try_new_in(capacity, Global)
+    }
+    /// Same as `new_growable` but returns None instead of panicking
+    pub fn try_new_growable(capacity: usize, growth_factor: usize) -> Option<Self> {
+        Self::try_new_growable_in(capacity, growth_factor, Global)
+    }
+}
+
+impl<T, A> Stack<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
 {
     #[inline(always)]
     pub fn capacity(&self) -> usize {
@@ -27,21 +67,61 @@ where
     pub fn len(&self) -> usize {
         self.len
     }
-    pub fn new(capacity: usize) -> Self {
-        let layout = Layout::array::<T>(capacity).expect("Failed to create layout");
-        let data = unsafe { std::alloc::alloc(layout) as *mut T };
+    pub fn new_in(capacity: usize, alloc: A) -> Self {
+        if Layout::array::<T>(capacity).is_err() {
+            panic!("Failed to create layout");
+        }
+        Self::try_new_in(capacity, alloc).expect("Failed to allocate memory")
+    }
+    /// Same as `new_in` but returns None instead of panicking when the layout overflows or the allocation fails
+    pub fn try_new_in(capacity: usize, alloc: A) -> Option<Self> {
+        let layout = Layout::array::<T>(capacity).ok()?;
+        let data = unsafe { alloc.alloc(layout) as *mut T };
         if data.is_null() {
-            panic!("Failed to allocate memory");
+            return None;
         }
 
-        Stack {
+        Some(Stack {
             capacity,
             layout,
             data,
             len: 0,
             top: unsafe { data.offset(-1) },
             end: unsafe { data.add(capacity) },
+            growth_factor: 0,
+            alloc,
+        })
+    }
+    pub fn new_growable_in(capacity: usize, growth_factor: usize, alloc: A) -> Self {
+        if growth_factor < 2 {
+            panic!("Growth factor must be at least 2");
         }
+        let mut stack = Self::new_in(capacity, alloc);
+        stack.growth_factor = growth_factor;
+        stack
+    }
+    /// Same as `new_growable_in` but returns None when the growth factor is below 2, the layout
+    /// overflows or the allocation fails
+    pub fn try_new_growable_in(capacity: usize, growth_factor: usize, alloc: A) -> Option<Self> {
+        if growth_factor < 2 {
+            return None;
+        }
+        let mut stack = Self::try_new_in(capacity, alloc)?;
+        stack.growth_factor = growth_factor;
+        Some(stack)
+    }
+
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+    #[inline(always)]
+    pub fn is_growable(&self) -> bool {
+        self.growth_factor != 0
+    }
+    #[inline(always)]
+    pub fn growth_factor(&self) -> usize {
+        self.growth_factor
     }
 
     #[inline(always)]
@@ -52,7 +132,7 @@ where
     pub fn extend(&mut self, new_capacity: usize) {
         let new_layout = Layout::array::<T>(new_capacity).expect("Failed to create layout");
         unsafe {
-            self.data = realloc(self.data as *mut u8, new_layout, new_layout.size()) as *mut T;
+            self.data = self.alloc.realloc(self.data as *mut u8, self.layout, new_layout.size()) as *mut T;
             if self.data.is_null() {
                 panic!("Failed to reallocate memory");
             }
@@ -80,16 +160,38 @@ where
         }
         unsafe { Some(self.top.as_mut().unwrap()) }
     }
+    #[cold]
+    fn grow(&mut self) {
+        if self.growth_factor == 0 {
+            panic!("Stack over capacity!");
+        }
+        let new_capacity = self.capacity.checked_mul(self.growth_factor).expect("Stack capacity overflow");
+        self.extend(new_capacity.max(1));
+    }
     #[inline(always)]
     pub fn push(&mut self, value: T) {
+        let mut new_top = unsafe { self.top.offset(1) };
+        if new_top == self.end {
+            self.grow();
+            new_top = unsafe { self.top.offset(1) };
+        }
+
+        self.top = new_top;
+        unsafe { self.top.write(value) };
+        self.len += 1;
+    }
+    /// Never grows. Hands the value back if the stack is full.
+    #[inline(always)]
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
         let new_top = unsafe { self.top.offset(1) };
         if new_top == self.end {
-            panic!("Stack over capacity!");
+            return Err(value);
         }
 
         self.top = new_top;
         unsafe { self.top.write(value) };
         self.len += 1;
+        Ok(())
     }
     #[inline(always)]
     pub fn pop(&mut self) -> Option<T> {
@@ -104,11 +206,138 @@ where
             result
         }
     }
+
+    /// Pushes the whole slice with a single copy. The last element of the slice ends up on top.
+    pub fn push_slice(&mut self, values: &[T]) {
+        let required = self.len + values.len();
+        if required > self.capacity {
+            if self.growth_factor == 0 {
+                panic!("Stack over capacity!");
+            }
+            let new_capacity = self.capacity.checked_mul(self.growth_factor).expect("Stack capacity overflow");
+            self.extend(required.max(new_capacity));
+        }
+        unsafe {
+            ptr::copy_nonoverlapping(values.as_ptr(), self.top.offset(1), values.len());
+            self.top = self.top.add(values.len());
+        }
+        self.len = required;
+    }
+    /// Pops the top `n` elements and returns them in bottom to top order.
+    /// The slice stays valid until the stack is modified again.
+    pub fn pop_n(&mut self, n: usize) -> &[T] {
+        if n > self.len {
+            panic!("Cannot pop more elements than the stack holds");
+        }
+        self.len -= n;
+        unsafe {
+            self.top = self.top.sub(n);
+            slice::from_raw_parts(self.top.offset(1), n)
+        }
+    }
+    /// Pops up to `values.len()` elements into the slice in bottom to top order.
+    /// Returns the number of popped elements.
+    pub fn pop_into(&mut self, values: &mut [T]) -> usize {
+        let n = values.len().min(self.len);
+        let popped = self.pop_n(n);
+        values[..n].copy_from_slice(popped);
+        n
+    }
+    #[inline(always)]
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        self.top = unsafe { self.data.offset(len as isize - 1) };
+        self.len = len;
+    }
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+    /// Bottom to top order
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+    /// Bottom to top order
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+    }
+    #[inline(always)]
+    pub fn iter(&self) -> StackIterator<'_, T> {
+        self.as_slice().iter().rev()
+    }
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> StackIteratorMut<'_, T> {
+        self.as_mut_slice().iter_mut().rev()
+    }
+}
+
+const TAG: &[u8; 4] = b"ETST";
+
+/// Header with the element size, capacity, growth factor and length, then the elements
+/// from bottom to top as raw native-endian bytes. Reading on a target with the other byte order
+/// fails with `InvalidData`.
+impl<T> Stack<T>
+where
+    T: Pod,
+{
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let header = [size_of::<T>() as u64, self.capacity as u64, self.growth_factor as u64, self.len as u64];
+        write_header(writer, TAG, ByteOrder::Native, &header)?;
+        let bytes = as_bytes(self.as_slice());
+        writer.write_all(bytes)?;
+        write_padding(writer, bytes.len())
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let [element_size, capacity, growth_factor, len] = read_header(reader, TAG, ByteOrder::Native)?;
+        check_element_size::<T>(element_size)?;
+        let (capacity, growth_factor, len) = (to_usize(capacity)?, to_usize(growth_factor)?, to_usize(len)?);
+        if len > capacity || growth_factor == 1 {
+            return Err(invalid_data("Invalid stack header"));
+        }
+        let bytes = read_payload(reader, payload_len::<T>(len)?)?;
+        let mut stack = Self::try_new(capacity).ok_or_else(|| invalid_data("Capacity too large"))?;
+        stack.growth_factor = growth_factor;
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), stack.data as *mut u8, bytes.len()) };
+        stack.len = len;
+        stack.top = unsafe { stack.data.offset(len as isize - 1) };
+        Ok(stack)
+    }
+}
+
+impl<T, A> Extend<T> for Stack<T, A>
+where
+    T: Copy + Sized,
+    A: RawAlloc,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// The collected stack is growable since the iterator length is not known up front.
+impl<T> FromIterator<T> for Stack<T>
+where
+    T: Copy + Sized,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut stack = Self::new_growable(iter.size_hint().0.max(1), 2);
+        Extend::extend(&mut stack, iter);
+        stack
+    }
 }
 
-impl<T> Debug for Stack<T>
+impl<T, A> Debug for Stack<T, A>
 where
     T: Copy + Sized + Debug,
+    A: RawAlloc,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Stack [len={}, cap={}] [", self.len, self.capacity)?;
@@ -126,9 +355,10 @@ where
     }
 }
 
-impl<T> Index<isize> for Stack<T>
+impl<T, A> Index<isize> for Stack<T, A>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     type Output = T;
     #[inline(always)]
@@ -146,9 +376,10 @@ where
     }
 }
 
-impl<T> IndexMut<isize> for Stack<T>
+impl<T, A> IndexMut<isize> for Stack<T, A>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     #[inline(always)]
     fn index_mut(&mut self, index: isize) -> &mut Self::Output {
@@ -165,13 +396,14 @@ where
     }
 }
 
-impl<T> Drop for Stack<T>
+impl<T, A> Drop for Stack<T, A>
 where
     T: Copy + Sized,
+    A: RawAlloc,
 {
     fn drop(&mut self) {
         unsafe {
-            std::alloc::dealloc(self.data as *mut u8, self.layout);
+            self.alloc.dealloc(self.data as *mut u8, self.layout);
         }
     }
 }
@@ -0,0 +1,144 @@
+use std::alloc::Layout;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::data_structs::array::Array;
+use crate::data_structs::bitmap::Bitmap;
+use crate::data_structs::queue::Queue;
+use crate::data_structs::raw_alloc::{Global, RawAlloc};
+use crate::data_structs::stack::Stack;
+
+/// Forwards to the global allocator and keeps track of what is still allocated
+#[derive(Default)]
+struct CountingAlloc {
+    allocations: AtomicUsize,
+    deallocations: AtomicUsize,
+    live_bytes: AtomicUsize,
+}
+
+impl CountingAlloc {
+    fn assert_no_leaks(&self) {
+        assert_eq!(self.live_bytes.load(Ordering::Relaxed), 0);
+        assert_eq!(self.allocations.load(Ordering::Relaxed), self.deallocations.load(Ordering::Relaxed));
+    }
+}
+
+unsafe impl RawAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.live_bytes.fetch_add(layout.size(), Ordering::Relaxed);
+        Global.alloc(layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        self.live_bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+        self.live_bytes.fetch_add(new_size, Ordering::Relaxed);
+        Global.realloc(ptr, layout, new_size)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.deallocations.fetch_add(1, Ordering::Relaxed);
+        self.live_bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+        Global.dealloc(ptr, layout)
+    }
+}
+
+#[test]
+fn test_array_allocator() {
+    let counter = CountingAlloc::default();
+    {
+        let mut array = Array::<u64, _>::new_in(4, &counter);
+        assert_eq!(counter.live_bytes.load(Ordering::Relaxed), 32);
+        array.fill(7);
+        array.resize(16);
+        assert_eq!(counter.live_bytes.load(Ordering::Relaxed), 128);
+        assert_eq!(array[3], 7);
+        array.resize(2);
+        assert_eq!(counter.live_bytes.load(Ordering::Relaxed), 16);
+
+        let copy = array.clone();
+        assert_eq!(copy.as_slice(), &[7, 7]);
+        let from_slice = Array::from_slice_in(&[1u8, 2, 3], &counter);
+        assert_eq!(from_slice.as_slice(), &[1, 2, 3]);
+        assert_eq!(counter.allocations.load(Ordering::Relaxed), 3);
+    }
+    counter.assert_no_leaks();
+}
+
+#[test]
+fn test_stack_allocator() {
+    let counter = CountingAlloc::default();
+    {
+        let mut stack = Stack::new_growable_in(2, 2, &counter);
+        for i in 0..100 {
+            stack.push(i);
+        }
+        assert_eq!(stack.capacity(), 128);
+        assert_eq!(counter.live_bytes.load(Ordering::Relaxed), 128 * 4);
+        assert_eq!(stack.pop(), Some(99));
+
+        let mut fixed = Stack::<u16, _>::new_in(0, &counter);
+        fixed.extend_by(8);
+        fixed.push(1);
+        assert_eq!(fixed.top(), Some(&1));
+    }
+    counter.assert_no_leaks();
+}
+
+#[test]
+fn test_queue_allocator() {
+    let counter = CountingAlloc::default();
+    {
+        let mut queue = Queue::new_pow2_sized_growable_in(4, &counter);
+        queue.push(0u32);
+        queue.push(1);
+        queue.push(2);
+        assert_eq!(queue.dequeue(), Some(0));
+        assert_eq!(queue.dequeue(), Some(1));
+        // Wraps around and grows through the copying path
+        for i in 3..8 {
+            queue.push(i);
+        }
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), (2..8).collect::<Vec<_>>());
+        assert_eq!(counter.live_bytes.load(Ordering::Relaxed), 8 * 4);
+
+        let copy = queue.clone();
+        assert_eq!(copy.len(), 6);
+        queue.extend_pow2_sized(64);
+        assert_eq!(queue.drain().count(), 6);
+    }
+    counter.assert_no_leaks();
+}
+
+#[test]
+fn test_bitmap_allocator() {
+    let counter = CountingAlloc::default();
+    {
+        let mut bitmap = Bitmap::with_capacity_in(0, &counter);
+        for i in 0..500 {
+            bitmap.push(i % 3 == 0);
+        }
+        bitmap.resize(2000, true);
+        bitmap.truncate(100);
+        bitmap.shrink_to_fit();
+
+        let other = Bitmap::new_in(300, &counter);
+        let union = &bitmap | &other;
+        assert_eq!(union.bit_capacity(), 300);
+        assert_eq!(union.count_ones(0, 300), 34);
+        let negated = !&bitmap;
+        assert_eq!(negated.count_ones(0, 100), 66);
+        bitmap.and_not_assign(&Bitmap::new(100));
+    }
+    counter.assert_no_leaks();
+}
+
+#[test]
+fn test_zero_sized_allocations() {
+    let counter = CountingAlloc::default();
+    {
+        let mut array = Array::<u32, _>::new_in(0, &counter);
+        array.resize(4);
+        array.resize(0);
+        let _stack = Stack::<u64, _>::new_in(0, &counter);
+    }
+    counter.assert_no_leaks();
+}
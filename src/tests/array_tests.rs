@@ -140,8 +140,9 @@ pub fn split_at_test() {
         array[i] = i as i32;
     }
     let (left, right) = array.split_at(5);
-    for i in 0..5 {
-        assert_eq!(left[i], i as i32);
+    assert_eq!(left.len(), 5);
+    for (i, value) in left.iter().enumerate() {
+        assert_eq!(*value, i as i32);
     }
 
     for i in 5..10 {
@@ -156,9 +157,10 @@ pub fn split_at_mut_test() {
         array[i] = i as i32;
     }
     let (left, right) = array.split_at_mut(5);
-    for i in 0..5 {
-        left[i] += 10;
-        assert_eq!(left[i], (i + 10) as i32);
+    assert_eq!(left.len(), 5);
+    for (i, value) in left.iter_mut().enumerate() {
+        *value += 10;
+        assert_eq!(*value, (i + 10) as i32);
     }
 
     for i in 5..10 {
@@ -175,9 +177,10 @@ pub fn split_into_parts_test() {
     }
     let parts = array.split_into_parts(5);
     assert_eq!(parts.capacity(), 5);
-    for i in 0..5 {
-        for j in 0..20 {
-            assert_eq!(parts[i][j], (i * 20 + j) as i32);
+    for (i, part) in parts.iter().enumerate() {
+        assert_eq!(part.len(), 20);
+        for (j, value) in part.iter().enumerate() {
+            assert_eq!(*value, (i * 20 + j) as i32);
         }
     }
 }
@@ -190,10 +193,11 @@ pub fn split_into_parts_mut_test() {
     }
     let mut parts = array.split_into_parts_mut(5);
     assert_eq!(parts.capacity(), 5);
-    for i in 0..5 {
-        for j in 0..20 {
-            parts[i][j] = (1000 + i * 20 + j) as i32;
-            assert_eq!(parts[i][j], (1000 + i * 20 + j) as i32);
+    for (i, part) in parts.iter_mut().enumerate() {
+        assert_eq!(part.len(), 20);
+        for (j, value) in part.iter_mut().enumerate() {
+            *value = (1000 + i * 20 + j) as i32;
+            assert_eq!(*value, (1000 + i * 20 + j) as i32);
         }
     }
 }
@@ -1,5 +1,8 @@
+use std::sync::Arc;
+use std::thread;
+
 use crate::data_structs::bitmap::atomic_bitmap::AtomicBitmap;
-use crate::data_structs::bitmap::atomic_bitmap::Mode::Relaxed;
+use crate::data_structs::bitmap::atomic_bitmap::Mode::{Relaxed, Strict};
 use crate::data_structs::bitmap::handle::Handle;
 use crate::data_structs::bitmap::Bitmap;
 
@@ -164,7 +167,7 @@ fn bitmap_batch_test_single_false() {
     bitmap.set(7, true, Relaxed);
     bitmap.set(8, false, Relaxed);
     let handles = Handle::new_batch(&[0, 1, 2, 4, 5, 7, 8]);
-    assert_eq!(bitmap.check_batch(handles.as_slice(), Relaxed), false);
+    assert!(!bitmap.check_batch(handles.as_slice(), Relaxed));
 }
 
 #[test]
@@ -174,7 +177,7 @@ fn bitmap_test_batch() {
         bitmap.set(i, i % 2 == 0, Relaxed);
     }
     let handles = Handle::new_batch(&[0, 4, 1022]);
-    assert_eq!(bitmap.check_batch(handles.as_slice(), Relaxed), true);
+    assert!(bitmap.check_batch(handles.as_slice(), Relaxed));
 }
 
 #[test]
@@ -184,7 +187,7 @@ fn bitmap_test_batch_fail() {
         bitmap.set(i, i % 2 == 0, Relaxed);
     }
     let handles = Handle::new_batch(&[0, 4, 1023]);
-    assert_eq!(bitmap.check_batch(handles.as_slice(), Relaxed), false);
+    assert!(!bitmap.check_batch(handles.as_slice(), Relaxed));
 }
 
 #[test]
@@ -194,5 +197,197 @@ fn bitmap_test_batch_overlapping() {
         bitmap.set(i, i % 2 == 0, Relaxed);
     }
     let handles = Handle::new_batch(&[0, 4, 6, 8, 10, 12, 14, 1022]);
-    assert_eq!(bitmap.check_batch(handles.as_slice(), Relaxed), true);
+    assert!(bitmap.check_batch(handles.as_slice(), Relaxed));
+}
+
+#[test]
+fn atomic_bitmap_fetch_ops_test() {
+    let bitmap = AtomicBitmap::new(100);
+    assert!(!bitmap.fetch_set(70, Strict));
+    assert!(bitmap.fetch_set(70, Strict));
+    assert_eq!(bitmap.get(70, Strict), Some(true));
+
+    assert!(bitmap.fetch_clear(70, Relaxed));
+    assert!(!bitmap.fetch_clear(70, Relaxed));
+    assert_eq!(bitmap.get(70, Relaxed), Some(false));
+
+    assert!(!bitmap.fetch_toggle(3, Relaxed));
+    assert_eq!(bitmap.get(3, Relaxed), Some(true));
+    assert!(bitmap.fetch_toggle(3, Relaxed));
+    assert_eq!(bitmap.get(3, Relaxed), Some(false));
+
+    assert!(!bitmap.test_and_set(5, Strict));
+    assert!(bitmap.test_and_set(5, Strict));
+}
+
+#[test]
+fn atomic_bitmap_set_if_test() {
+    let bitmap = AtomicBitmap::new(64);
+    bitmap.set(1, true, Relaxed);
+    assert!(!bitmap.set_if(0, true, false, Strict));
+    assert!(bitmap.set_if(0, false, true, Strict));
+    assert_eq!(bitmap.get(0, Strict), Some(true));
+    assert!(bitmap.set_if(0, true, false, Strict));
+    assert_eq!(bitmap.get(0, Strict), Some(false));
+    assert_eq!(bitmap.get(1, Strict), Some(true));
+}
+
+#[test]
+#[should_panic(expected = "Bit index out of bounds")]
+fn atomic_bitmap_fetch_set_out_of_bounds_test() {
+    let bitmap = AtomicBitmap::new(10);
+    bitmap.fetch_set(10, Relaxed);
+}
+
+#[test]
+fn atomic_bitmap_concurrent_set_same_word_test() {
+    const THREADS: usize = 4;
+    let bitmap = Arc::new(AtomicBitmap::new(64));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread| {
+            let bitmap = bitmap.clone();
+            thread::spawn(move || {
+                for round in 0..1_000 {
+                    for bit in (thread..64).step_by(THREADS) {
+                        bitmap.set(bit, round % 2 == 0, Relaxed);
+                    }
+                }
+                for bit in (thread..64).step_by(THREADS) {
+                    bitmap.set(bit, true, Relaxed);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(bitmap.to_indices_true(Strict).len(), 64);
+}
+
+#[test]
+fn atomic_bitmap_test_and_set_claims_once_test() {
+    const THREADS: usize = 4;
+    let bitmap = Arc::new(AtomicBitmap::new(1_000));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let bitmap = bitmap.clone();
+            thread::spawn(move || (0..1_000).filter(|bit| !bitmap.test_and_set(*bit, Strict)).count())
+        })
+        .collect();
+    let claimed: usize = handles.into_iter().map(|handle| handle.join().unwrap()).sum();
+    assert_eq!(claimed, 1_000);
+}
+
+#[test]
+fn atomic_bitmap_count_test() {
+    let bitmap = AtomicBitmap::new(200);
+    for bit in [0, 5, 63, 64, 130, 199] {
+        bitmap.set(bit, true, Relaxed);
+    }
+    assert_eq!(bitmap.count_ones(0, 200, Strict), 6);
+    assert_eq!(bitmap.count_zeros(0, 200, Strict), 194);
+    assert_eq!(bitmap.count_ones(1, 64, Relaxed), 2);
+    assert_eq!(bitmap.count_ones(63, 65, Relaxed), 2);
+    assert_eq!(bitmap.count_ones(131, 199, Relaxed), 0);
+    assert_eq!(bitmap.count_zeros(131, 199, Relaxed), 68);
+    assert_eq!(bitmap.count_ones(10, 10, Relaxed), 0);
+}
+
+#[test]
+#[should_panic(expected = "Upper bound out of bounds")]
+fn atomic_bitmap_count_out_of_bounds_test() {
+    let bitmap = AtomicBitmap::new(100);
+    bitmap.count_ones(0, 101, Relaxed);
+}
+
+#[test]
+fn atomic_bitmap_first_test() {
+    let bitmap = AtomicBitmap::new(200);
+    assert_eq!(bitmap.first_one(0, Strict), None);
+    assert_eq!(bitmap.first_zero(0, Strict), Some(0));
+    bitmap.set(70, true, Relaxed);
+    bitmap.set(150, true, Relaxed);
+    assert_eq!(bitmap.first_one(0, Strict), Some(70));
+    assert_eq!(bitmap.first_one(71, Strict), Some(150));
+    assert_eq!(bitmap.first_one(151, Strict), None);
+    assert_eq!(bitmap.first_one_bounds(71, 150, Relaxed), None);
+    assert_eq!(bitmap.first_one_bounds(71, 151, Relaxed), Some(150));
+
+    for bit in 0..200 {
+        bitmap.set(bit, true, Relaxed);
+    }
+    assert_eq!(bitmap.first_zero(0, Strict), None);
+    bitmap.set(100, false, Relaxed);
+    assert_eq!(bitmap.first_zero(0, Strict), Some(100));
+    assert_eq!(bitmap.first_zero(101, Strict), None);
+    assert_eq!(bitmap.first_zero_bounds(0, 100, Relaxed), None);
+    assert_eq!(bitmap.first_zero_bounds(0, 101, Relaxed), Some(100));
+}
+
+#[test]
+fn atomic_bitmap_snapshot_test() {
+    let bitmap = AtomicBitmap::new(150);
+    for bit in [1, 64, 100, 149] {
+        bitmap.set(bit, true, Relaxed);
+    }
+    let snapshot = bitmap.snapshot(Strict);
+    assert_eq!(snapshot.bit_capacity(), 150);
+    assert_eq!(snapshot.to_indices_true(), vec![1, 64, 100, 149]);
+    assert_eq!(snapshot.to_indices_true(), bitmap.to_indices_true(Strict));
+
+    // The snapshot does not follow later writes
+    bitmap.set(2, true, Relaxed);
+    assert_eq!(snapshot.get(2), Some(false));
+}
+
+#[test]
+fn atomic_bitmap_batch_ops_test() {
+    let bitmap = AtomicBitmap::new(100_000);
+    let offsets = [3, 16_384, 16_447, 70_000, 99_999];
+    let handles = Handle::new_atomic_batch_checked(&offsets, &bitmap);
+    assert!(!bitmap.check_batch_any(handles.as_slice(), Strict));
+
+    bitmap.set_batch(handles.as_slice(), Strict);
+    assert_eq!(bitmap.to_indices_true(Strict), offsets.to_vec());
+    assert!(bitmap.check_batch(handles.as_slice(), Strict));
+
+    bitmap.clear_batch(&handles.as_slice()[..2], Relaxed);
+    assert_eq!(bitmap.to_indices_true(Relaxed), vec![70_000, 99_999]);
+    assert!(!bitmap.check_batch(handles.as_slice(), Relaxed));
+    assert!(bitmap.check_batch_any(handles.as_slice(), Relaxed));
+
+    bitmap.toggle_batch(handles.as_slice(), Relaxed);
+    assert_eq!(bitmap.to_indices_true(Relaxed), vec![3, 16_384, 16_447]);
+}
+
+#[test]
+#[should_panic(expected = "Handle out of bounds")]
+fn atomic_bitmap_check_batch_out_of_bounds_test() {
+    let bitmap = AtomicBitmap::new(64);
+    let handles = Handle::new_batch(&[1_000]);
+    bitmap.check_batch(handles.as_slice(), Relaxed);
+}
+
+#[test]
+fn atomic_bitmap_concurrent_set_batch_test() {
+    const THREADS: usize = 4;
+    let bitmap = Arc::new(AtomicBitmap::new(256));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread| {
+            let bitmap = bitmap.clone();
+            thread::spawn(move || {
+                let offsets: Vec<usize> = (thread..256).step_by(THREADS).collect();
+                let handles = Handle::new_atomic_batch_checked(&offsets, &bitmap);
+                for _ in 0..1_000 {
+                    bitmap.set_batch(handles.as_slice(), Strict);
+                    bitmap.clear_batch(handles.as_slice(), Strict);
+                }
+                bitmap.set_batch(handles.as_slice(), Strict);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(bitmap.count_ones(0, 256, Strict), 256);
 }
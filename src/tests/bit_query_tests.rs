@@ -0,0 +1,80 @@
+use crate::data_structs::bitmap::atomic_bitmap::AtomicBitmap;
+use crate::data_structs::bitmap::atomic_bitmap::Mode::Strict;
+use crate::data_structs::bitmap::query::BitQuery;
+use crate::data_structs::bitmap::Bitmap;
+
+#[test]
+fn test_query_compile_groups_words() {
+    let query = BitQuery::new(&[0, 1, 70], &[2, 20_000], &[71, 72]);
+    let words = query.words();
+    assert_eq!(words.len(), 3);
+    assert_eq!(words[0].chunk, 0);
+    assert_eq!(words[0].required, 0b11);
+    assert_eq!(words[0].forbidden, 0b100);
+    assert_eq!(words[1].chunk, 1);
+    assert_eq!(words[1].required, 1 << 6);
+    assert_eq!(words[1].any_of, 0b11 << 7);
+    assert_eq!(words[2].chunk, 20_000 >> 6);
+    assert_eq!(query.bit_len(), 20_001);
+}
+
+#[test]
+fn test_query_bitmap() {
+    let mut bitmap = Bitmap::new(30_000);
+    let query = BitQuery::new(&[0, 1, 70], &[2, 20_000], &[71, 72]);
+    assert!(!bitmap.matches(&query));
+
+    bitmap.set(0, true);
+    bitmap.set(1, true);
+    bitmap.set(70, true);
+    // No any-of bit yet
+    assert!(!bitmap.matches(&query));
+    bitmap.set(72, true);
+    assert!(bitmap.matches(&query));
+
+    bitmap.set(20_000, true);
+    assert!(!bitmap.matches(&query));
+    bitmap.set(20_000, false);
+    bitmap.set(2, true);
+    assert!(!bitmap.matches(&query));
+}
+
+#[test]
+fn test_query_shorthands() {
+    let mut bitmap = Bitmap::new(200);
+    bitmap.set(5, true);
+    bitmap.set(150, true);
+    assert!(bitmap.matches(&BitQuery::all_of(&[5, 150])));
+    assert!(!bitmap.matches(&BitQuery::all_of(&[5, 151])));
+    assert!(bitmap.matches(&BitQuery::none_of(&[6, 151])));
+    assert!(!bitmap.matches(&BitQuery::none_of(&[6, 150])));
+    assert!(bitmap.matches(&BitQuery::any_of(&[6, 150])));
+    assert!(!bitmap.matches(&BitQuery::any_of(&[6, 151])));
+    assert!(bitmap.matches(&BitQuery::new(&[], &[], &[])));
+}
+
+#[test]
+fn test_query_atomic_bitmap() {
+    let bitmap = AtomicBitmap::new(1_000);
+    let query = BitQuery::new(&[10, 900], &[11], &[500, 501]);
+    bitmap.set(10, true, Strict);
+    bitmap.set(900, true, Strict);
+    assert!(!bitmap.matches(&query, Strict));
+    bitmap.set(501, true, Strict);
+    assert!(bitmap.matches(&query, Strict));
+    bitmap.set(11, true, Strict);
+    assert!(!bitmap.matches(&query, Strict));
+}
+
+#[test]
+#[should_panic(expected = "Query out of bounds")]
+fn test_query_out_of_bounds() {
+    let bitmap = Bitmap::new(100);
+    bitmap.matches(&BitQuery::all_of(&[100]));
+}
+
+#[test]
+#[should_panic(expected = "Bit cannot be both required and forbidden")]
+fn test_query_contradiction() {
+    BitQuery::new(&[3], &[3], &[]);
+}
@@ -1,4 +1,5 @@
 use crate::data_structs::bitmap::handle::Handle;
+use crate::data_structs::bitmap::rank_select::RankSelectBitmap;
 use crate::data_structs::bitmap::Bitmap;
 
 #[test]
@@ -163,7 +164,7 @@ fn bitmap_batch_test_single_false() {
     bitmap.set(7, true);
     bitmap.set(8, false);
     let handles = Handle::new_batch(&[0, 1, 2, 4, 5, 7, 8]);
-    assert_eq!(bitmap.check_batch(handles.as_slice()), false);
+    assert!(!bitmap.check_batch(handles.as_slice()));
 }
 
 #[test]
@@ -173,7 +174,7 @@ fn bitmap_test_batch() {
         bitmap.set(i, i % 2 == 0);
     }
     let handles = Handle::new_batch(&[0, 4, 1022]);
-    assert_eq!(bitmap.check_batch(handles.as_slice()), true);
+    assert!(bitmap.check_batch(handles.as_slice()));
 }
 
 #[test]
@@ -183,7 +184,7 @@ fn bitmap_test_batch_fail() {
         bitmap.set(i, i % 2 == 0);
     }
     let handles = Handle::new_batch(&[0, 4, 1023]);
-    assert_eq!(bitmap.check_batch(handles.as_slice()), false);
+    assert!(!bitmap.check_batch(handles.as_slice()));
 }
 
 #[test]
@@ -193,7 +194,7 @@ fn bitmap_test_batch_overlapping() {
         bitmap.set(i, i % 2 == 0);
     }
     let handles = Handle::new_batch(&[0, 4, 6, 8, 10, 12, 14, 1022]);
-    assert_eq!(bitmap.check_batch(handles.as_slice()), true);
+    assert!(bitmap.check_batch(handles.as_slice()));
 }
 
 #[test]
@@ -793,3 +794,640 @@ fn bitmap_test_count_zero_length_ranges() {
     assert_eq!(bitmap.count_zeros(50, 50), 0);
     assert_eq!(bitmap.count_zeros(99, 99), 0);
 }
+
+fn bitmap_from_indices(bit_count: usize, indices: &[usize]) -> Bitmap {
+    let mut bitmap = Bitmap::new(bit_count);
+    for index in indices {
+        bitmap.set(*index, true);
+    }
+    bitmap
+}
+
+#[test]
+fn bitmap_clone_test() {
+    let bitmap = bitmap_from_indices(130, &[0, 64, 129]);
+    let mut cloned = bitmap.clone();
+    cloned.set(1, true);
+    assert_eq!(bitmap.to_indices_true(), vec![0, 64, 129]);
+    assert_eq!(cloned.to_indices_true(), vec![0, 1, 64, 129]);
+}
+
+#[test]
+fn bitmap_and_or_xor_test() {
+    let a = bitmap_from_indices(200, &[0, 5, 63, 64, 100, 199]);
+    let b = bitmap_from_indices(200, &[5, 63, 65, 100, 150]);
+    assert_eq!((&a & &b).to_indices_true(), vec![5, 63, 100]);
+    assert_eq!((&a | &b).to_indices_true(), vec![0, 5, 63, 64, 65, 100, 150, 199]);
+    assert_eq!((&a ^ &b).to_indices_true(), vec![0, 64, 65, 150, 199]);
+    assert_eq!(a.and_not(&b).to_indices_true(), vec![0, 64, 199]);
+}
+
+#[test]
+fn bitmap_assign_ops_test() {
+    let b = bitmap_from_indices(200, &[5, 63, 65, 100, 150]);
+    let mut a = bitmap_from_indices(200, &[0, 5, 63, 64, 100, 199]);
+    a &= &b;
+    assert_eq!(a.to_indices_true(), vec![5, 63, 100]);
+    a |= &bitmap_from_indices(200, &[1, 2]);
+    assert_eq!(a.to_indices_true(), vec![1, 2, 5, 63, 100]);
+    a ^= &bitmap_from_indices(200, &[2, 3]);
+    assert_eq!(a.to_indices_true(), vec![1, 3, 5, 63, 100]);
+    a.and_not_assign(&b);
+    assert_eq!(a.to_indices_true(), vec![1, 3]);
+}
+
+#[test]
+fn bitmap_not_test() {
+    let a = bitmap_from_indices(70, &[0, 1, 65]);
+    let not_a = !&a;
+    assert_eq!(not_a.bit_capacity(), 70);
+    // Bits past bit_capacity must stay clear so the count matches
+    assert_eq!(not_a.count_ones(0, 70), 67);
+    assert_eq!(not_a.to_indices_false(), vec![0, 1, 65]);
+
+    let mut b = bitmap_from_indices(64, &[3]);
+    b.negate();
+    assert_eq!(b.count_ones(0, 64), 63);
+    assert_eq!(b.first_zero(0), Some(3));
+}
+
+#[test]
+fn bitmap_mismatched_capacity_test() {
+    let short = bitmap_from_indices(10, &[1, 2, 3]);
+    let long = bitmap_from_indices(150, &[2, 3, 4, 140]);
+
+    // Allocating ops produce the longer length
+    let and = &short & &long;
+    assert_eq!(and.bit_capacity(), 150);
+    assert_eq!(and.to_indices_true(), vec![2, 3]);
+    let or = &short | &long;
+    assert_eq!(or.bit_capacity(), 150);
+    assert_eq!(or.to_indices_true(), vec![1, 2, 3, 4, 140]);
+    let xor = &long ^ &short;
+    assert_eq!(xor.to_indices_true(), vec![1, 4, 140]);
+
+    // In-place ops keep the length of self and drop the bits that don't fit
+    let mut a = short.clone();
+    a |= &long;
+    assert_eq!(a.bit_capacity(), 10);
+    assert_eq!(a.to_indices_true(), vec![1, 2, 3, 4]);
+    assert_eq!(a.count_ones(0, 10), 4);
+
+    let mut b = long.clone();
+    b &= &short;
+    assert_eq!(b.to_indices_true(), vec![2, 3]);
+
+    let mut c = long.clone();
+    c.and_not_assign(&short);
+    assert_eq!(c.to_indices_true(), vec![4, 140]);
+}
+
+#[test]
+fn bitmap_not_full_word_test() {
+    let a = Bitmap::new(128);
+    let not_a = !&a;
+    assert_eq!(not_a.count_ones(0, 128), 128);
+    assert_eq!(not_a.first_zero(0), None);
+}
+
+#[test]
+fn bitmap_iter_ones_test() {
+    let bitmap = bitmap_from_indices(300, &[0, 1, 63, 64, 127, 128, 200, 299]);
+    assert_eq!(bitmap.iter_ones().collect::<Vec<_>>(), vec![0, 1, 63, 64, 127, 128, 200, 299]);
+    assert_eq!(Bitmap::new(300).iter_ones().next(), None);
+    assert_eq!(Bitmap::new(0).iter_ones().next(), None);
+}
+
+#[test]
+fn bitmap_iter_zeros_test() {
+    let mut bitmap = Bitmap::new(130);
+    for i in 0..130 {
+        bitmap.set(i, true);
+    }
+    bitmap.set(5, false);
+    bitmap.set(64, false);
+    bitmap.set(129, false);
+    // The padding bits past bit_capacity must not show up as zeros
+    assert_eq!(bitmap.iter_zeros().collect::<Vec<_>>(), vec![5, 64, 129]);
+}
+
+#[test]
+fn bitmap_iter_range_test() {
+    let bitmap = bitmap_from_indices(300, &[0, 1, 63, 64, 127, 128, 200, 299]);
+    assert_eq!(bitmap.iter_ones_range(1..128).collect::<Vec<_>>(), vec![1, 63, 64, 127]);
+    assert_eq!(bitmap.iter_ones_range(64..=128).collect::<Vec<_>>(), vec![64, 127, 128]);
+    assert_eq!(bitmap.iter_ones_range(2..63).next(), None);
+    assert_eq!(bitmap.iter_ones_range(5..5).next(), None);
+    assert_eq!(bitmap.iter_ones_range(201..).collect::<Vec<_>>(), vec![299]);
+    assert_eq!(bitmap.iter_zeros_range(..5).collect::<Vec<_>>(), vec![2, 3, 4]);
+    assert_eq!(bitmap.iter_zeros_range(60..66).collect::<Vec<_>>(), vec![60, 61, 62, 65]);
+}
+
+#[test]
+#[should_panic]
+fn bitmap_iter_range_out_of_bounds_test() {
+    let bitmap = Bitmap::new(100);
+    bitmap.iter_ones_range(0..101);
+}
+
+#[test]
+fn bitmap_iter_rev_test() {
+    let indices = [0, 1, 63, 64, 127, 128, 200, 299];
+    let bitmap = bitmap_from_indices(300, &indices);
+    let mut reversed = indices.to_vec();
+    reversed.reverse();
+    assert_eq!(bitmap.iter_ones().rev().collect::<Vec<_>>(), reversed);
+    assert_eq!(bitmap.iter_ones_range(1..128).rev().collect::<Vec<_>>(), vec![127, 64, 63, 1]);
+    assert_eq!(bitmap.iter_zeros_range(60..66).rev().collect::<Vec<_>>(), vec![65, 62, 61, 60]);
+}
+
+#[test]
+fn bitmap_iter_double_ended_test() {
+    let bitmap = bitmap_from_indices(300, &[0, 1, 63, 64, 127, 128, 200, 299]);
+    let mut iter = bitmap.iter_ones();
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next_back(), Some(299));
+    assert_eq!(iter.next_back(), Some(200));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(128));
+    assert_eq!(iter.next(), Some(63));
+    assert_eq!(iter.next(), Some(64));
+    assert_eq!(iter.next_back(), Some(127));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    // Both ends meeting inside a single word
+    let bitmap = bitmap_from_indices(64, &[3, 10, 20]);
+    let mut iter = bitmap.iter_ones();
+    assert_eq!(iter.next_back(), Some(20));
+    assert_eq!(iter.next(), Some(3));
+    assert_eq!(iter.next_back(), Some(10));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn bitmap_iter_matches_get_test() {
+    let mut bitmap = Bitmap::new(1000);
+    for i in 0..1000 {
+        bitmap.set(i, i % 7 == 0 || i % 11 == 3);
+    }
+    for (lower, upper) in [(0, 1000), (3, 997), (64, 128), (65, 127), (500, 501), (999, 1000)] {
+        let ones: Vec<usize> = (lower..upper).filter(|i| bitmap.get(*i).unwrap()).collect();
+        let zeros: Vec<usize> = (lower..upper).filter(|i| !bitmap.get(*i).unwrap()).collect();
+        assert_eq!(bitmap.iter_ones_range(lower..upper).collect::<Vec<_>>(), ones);
+        assert_eq!(bitmap.iter_zeros_range(lower..upper).collect::<Vec<_>>(), zeros);
+        assert_eq!(bitmap.to_indices_true_bounded(lower, upper), ones);
+        assert_eq!(bitmap.to_indices_false_bounded(lower, upper), zeros);
+    }
+}
+
+#[test]
+fn bitmap_with_capacity_test() {
+    let mut bitmap = Bitmap::with_capacity(1000);
+    assert_eq!(bitmap.bit_capacity(), 0);
+    assert_eq!(bitmap.capacity(), 16);
+    assert_eq!(bitmap.get(0), None);
+    for i in 0..1000 {
+        bitmap.push(i % 3 == 0);
+    }
+    assert_eq!(bitmap.capacity(), 16);
+    assert_eq!(bitmap.bit_capacity(), 1000);
+    assert_eq!(bitmap.count_ones(0, 1000), 334);
+}
+
+#[test]
+fn bitmap_push_test() {
+    let mut bitmap = Bitmap::new(0);
+    for i in 0..300 {
+        bitmap.push(i % 2 == 1);
+        assert_eq!(bitmap.bit_capacity(), i + 1);
+        assert_eq!(bitmap.get(i), Some(i % 2 == 1));
+    }
+    assert_eq!(bitmap.count_ones(0, 300), 150);
+    assert_eq!(bitmap.first_zero(299), None);
+    assert_eq!(bitmap.iter_ones().next_back(), Some(299));
+}
+
+#[test]
+fn bitmap_resize_grow_test() {
+    let mut bitmap = bitmap_from_indices(10, &[1, 9]);
+    bitmap.resize(200, false);
+    assert_eq!(bitmap.bit_capacity(), 200);
+    assert_eq!(bitmap.capacity(), 4);
+    assert_eq!(bitmap.to_indices_true(), vec![1, 9]);
+
+    let mut bitmap = bitmap_from_indices(10, &[1, 9]);
+    bitmap.resize(200, true);
+    assert_eq!(bitmap.count_ones(0, 200), 192);
+    assert_eq!(bitmap.to_indices_false(), vec![0, 2, 3, 4, 5, 6, 7, 8]);
+    // The padding past the new bit_capacity must stay clean
+    assert_eq!(bitmap.first_zero(100), None);
+    assert_eq!((!&bitmap).count_ones(0, 200), 8);
+}
+
+#[test]
+fn bitmap_resize_fill_within_word_test() {
+    let mut bitmap = Bitmap::new(3);
+    bitmap.resize(5, true);
+    assert_eq!(bitmap.to_indices_true(), vec![3, 4]);
+    bitmap.resize(64, true);
+    assert_eq!(bitmap.count_ones(0, 64), 61);
+    bitmap.resize(128, true);
+    assert_eq!(bitmap.count_ones(0, 128), 125);
+    assert_eq!(bitmap.first_zero(3), None);
+}
+
+#[test]
+fn bitmap_truncate_test() {
+    let mut bitmap = Bitmap::new(10);
+    bitmap.resize(300, true);
+    bitmap.truncate(400);
+    assert_eq!(bitmap.bit_capacity(), 300);
+    bitmap.truncate(70);
+    assert_eq!(bitmap.bit_capacity(), 70);
+    assert_eq!(bitmap.count_ones(0, 70), 60);
+    assert_eq!(bitmap.get(70), None);
+
+    // Growing back without fill must not resurrect the truncated bits
+    bitmap.resize(300, false);
+    assert_eq!(bitmap.count_ones(0, 300), 60);
+    assert_eq!(bitmap.first_one(70), None);
+    bitmap.shrink_to_fit();
+    assert_eq!(bitmap.capacity(), 5);
+    bitmap.truncate(0);
+    bitmap.shrink_to_fit();
+    assert_eq!(bitmap.capacity(), 1);
+    assert_eq!(bitmap.iter_ones().next(), None);
+}
+
+#[test]
+fn bitmap_ops_with_spare_capacity_test() {
+    let mut a = Bitmap::with_capacity(1000);
+    for i in 0..10 {
+        a.push(i % 2 == 0);
+    }
+    let b = bitmap_from_indices(10, &[0, 1]);
+    let cloned = a.clone();
+    assert_eq!(cloned.capacity(), 1);
+    assert_eq!((&a | &b).to_indices_true(), vec![0, 1, 2, 4, 6, 8]);
+    a.negate();
+    assert_eq!(a.to_indices_true(), vec![1, 3, 5, 7, 9]);
+    a.resize(1000, false);
+    assert_eq!(a.count_ones(0, 1000), 5);
+}
+
+#[test]
+fn bitmap_last_one_test() {
+    assert_eq!(Bitmap::new(0).last_one(), None);
+    assert_eq!(Bitmap::new(300).last_one(), None);
+    assert_eq!(bitmap_from_indices(300, &[0]).last_one(), Some(0));
+    assert_eq!(bitmap_from_indices(300, &[3, 64, 130]).last_one(), Some(130));
+    assert_eq!(bitmap_from_indices(300, &[3, 64, 299]).last_one(), Some(299));
+    assert_eq!(bitmap_from_indices(128, &[63, 127]).last_one(), Some(127));
+}
+
+#[test]
+fn bitmap_last_zero_test() {
+    assert_eq!(Bitmap::new(130).last_zero(), Some(129));
+    // Padding bits past bit_capacity are zero but must not be reported
+    let mut full = Bitmap::new(0);
+    full.resize(130, true);
+    assert_eq!(full.last_zero(), None);
+    full.set(64, false);
+    assert_eq!(full.last_zero(), Some(64));
+    full.set(0, false);
+    full.set(64, true);
+    assert_eq!(full.last_zero(), Some(0));
+}
+
+#[test]
+fn bitmap_prev_one_test() {
+    let bitmap = bitmap_from_indices(300, &[0, 63, 64, 200]);
+    assert_eq!(bitmap.prev_one(300), Some(200));
+    assert_eq!(bitmap.prev_one(200), Some(64));
+    assert_eq!(bitmap.prev_one(65), Some(64));
+    assert_eq!(bitmap.prev_one(64), Some(63));
+    assert_eq!(bitmap.prev_one(63), Some(0));
+    assert_eq!(bitmap.prev_one(0), None);
+    assert_eq!(bitmap_from_indices(300, &[5]).prev_one(5), None);
+}
+
+#[test]
+fn bitmap_prev_zero_test() {
+    let mut bitmap = Bitmap::new(0);
+    bitmap.resize(300, true);
+    bitmap.set(10, false);
+    bitmap.set(128, false);
+    assert_eq!(bitmap.prev_zero(300), Some(128));
+    assert_eq!(bitmap.prev_zero(128), Some(10));
+    assert_eq!(bitmap.prev_zero(10), None);
+    assert_eq!(bitmap.prev_zero(0), None);
+}
+
+#[test]
+#[should_panic]
+fn bitmap_prev_one_out_of_bounds_test() {
+    let bitmap = Bitmap::new(100);
+    bitmap.prev_one(101);
+}
+
+#[test]
+fn bitmap_last_bounds_test() {
+    let bitmap = bitmap_from_indices(300, &[0, 63, 64, 200]);
+    assert_eq!(bitmap.last_one_bounds(0, 300), Some(200));
+    assert_eq!(bitmap.last_one_bounds(0, 200), Some(64));
+    assert_eq!(bitmap.last_one_bounds(65, 200), None);
+    assert_eq!(bitmap.last_one_bounds(64, 65), Some(64));
+    assert_eq!(bitmap.last_zero_bounds(0, 300), Some(299));
+    assert_eq!(bitmap.last_zero_bounds(63, 65), None);
+    assert_eq!(bitmap.last_zero_bounds(0, 64), Some(62));
+}
+
+#[test]
+fn bitmap_last_bounds_narrow_range_test() {
+    let bitmap = bitmap_from_indices(300, &[0, 63, 64, 200]);
+    assert_eq!(bitmap.last_one_bounds(1, 63), None);
+    assert_eq!(bitmap.last_one_bounds(63, 64), Some(63));
+    assert_eq!(bitmap.last_one_bounds(130, 190), None);
+    assert_eq!(bitmap.last_one_bounds(201, 201), None);
+    assert_eq!(bitmap.last_zero_bounds(150, 151), Some(150));
+    assert_eq!(bitmap.last_zero_bounds(200, 201), None);
+
+    let mut bitmap = Bitmap::new(200);
+    for i in 0..200 {
+        bitmap.set(i, i % 7 == 0 || i % 11 == 0);
+    }
+    for lower_bound in 0..200 {
+        for upper_bound in lower_bound + 1..=200 {
+            let expected_one = (lower_bound..upper_bound).rev().find(|i| bitmap.get(*i).unwrap());
+            let expected_zero = (lower_bound..upper_bound).rev().find(|i| !bitmap.get(*i).unwrap());
+            assert_eq!(bitmap.last_one_bounds(lower_bound, upper_bound), expected_one);
+            assert_eq!(bitmap.last_zero_bounds(lower_bound, upper_bound), expected_zero);
+        }
+    }
+}
+
+#[test]
+fn bitmap_last_unchecked_test() {
+    let bitmap = bitmap_from_indices(300, &[0, 63, 64, 200]);
+    unsafe {
+        assert_eq!(bitmap.last_one_unchecked(199), Some(64));
+        assert_eq!(bitmap.last_one_unchecked(200), Some(200));
+        assert_eq!(bitmap.last_zero_unchecked(64), Some(62));
+        assert_eq!(bitmap.last_zero_unchecked(0), None);
+    }
+}
+
+#[test]
+fn bitmap_prev_matches_get_test() {
+    let mut bitmap = Bitmap::new(1000);
+    for i in 0..1000 {
+        bitmap.set(i, i % 97 == 5 || i % 131 == 0);
+    }
+    for before in 0..=1000 {
+        let one = (0..before).rev().find(|i| bitmap.get(*i).unwrap());
+        let zero = (0..before).rev().find(|i| !bitmap.get(*i).unwrap());
+        assert_eq!(bitmap.prev_one(before), one);
+        assert_eq!(bitmap.prev_zero(before), zero);
+    }
+}
+
+#[test]
+fn bitmap_flip_test() {
+    let mut bitmap = Bitmap::new(100);
+    bitmap.flip(3);
+    bitmap.flip(64);
+    assert_eq!(bitmap.to_indices_true(), vec![3, 64]);
+    bitmap.flip(3);
+    assert_eq!(bitmap.to_indices_true(), vec![64]);
+}
+
+#[test]
+#[should_panic]
+fn bitmap_flip_out_of_bounds_test() {
+    let mut bitmap = Bitmap::new(100);
+    bitmap.flip(100);
+}
+
+#[test]
+fn bitmap_set_range_test() {
+    let mut bitmap = Bitmap::new(300);
+    bitmap.set_range(3..7, true);
+    assert_eq!(bitmap.to_indices_true(), vec![3, 4, 5, 6]);
+    bitmap.set_range(60..200, true);
+    assert_eq!(bitmap.count_ones(0, 300), 144);
+    assert_eq!(bitmap.first_one(7), Some(60));
+    assert_eq!(bitmap.last_one(), Some(199));
+    bitmap.set_range(64..128, false);
+    assert_eq!(bitmap.count_ones(60, 200), 76);
+    assert_eq!(bitmap.first_zero(60), Some(64));
+    assert_eq!(bitmap.first_one(64), Some(128));
+    bitmap.set_range(.., true);
+    assert_eq!(bitmap.count_ones(0, 300), 300);
+    assert_eq!(bitmap.last_zero(), None);
+    bitmap.set_range(10..10, false);
+    assert_eq!(bitmap.count_ones(0, 300), 300);
+}
+
+#[test]
+fn bitmap_set_range_word_aligned_test() {
+    let mut bitmap = Bitmap::new(256);
+    bitmap.set_range(64..192, true);
+    assert_eq!(bitmap.first_one(0), Some(64));
+    assert_eq!(bitmap.last_one(), Some(191));
+    assert_eq!(bitmap.count_ones(0, 256), 128);
+    bitmap.set_range(0..256, false);
+    assert_eq!(bitmap.first_one(0), None);
+}
+
+#[test]
+#[should_panic]
+fn bitmap_set_range_out_of_bounds_test() {
+    let mut bitmap = Bitmap::new(100);
+    bitmap.set_range(50..101, true);
+}
+
+#[test]
+fn bitmap_flip_range_test() {
+    let mut bitmap = bitmap_from_indices(300, &[0, 5, 70, 250]);
+    bitmap.flip_range(4..7);
+    assert_eq!(bitmap.to_indices_true_bounded(0, 10), vec![0, 4, 6]);
+    bitmap.flip_range(60..260);
+    assert_eq!(bitmap.count_ones(60, 260), 198);
+    assert_eq!(bitmap.iter_zeros_range(60..260).collect::<Vec<_>>(), vec![70, 250]);
+    bitmap.flip_range(..);
+    assert_eq!(bitmap.count_ones(0, 300), 300 - 3 - 198);
+}
+
+#[test]
+fn bitmap_range_matches_set_test() {
+    for (lower, upper) in [(0, 1), (0, 64), (1, 63), (63, 65), (5, 300), (64, 128), (100, 299)] {
+        let mut ranged = Bitmap::new(300);
+        ranged.set_range(lower..upper, true);
+        let mut single = Bitmap::new(300);
+        for i in lower..upper {
+            single.set(i, true);
+        }
+        assert_eq!(ranged.to_indices_true(), single.to_indices_true());
+        ranged.flip_range(lower..upper);
+        assert_eq!(ranged.first_one(0), None);
+    }
+}
+
+#[test]
+fn bitmap_copy_range_from_test() {
+    let src = bitmap_from_indices(300, &[0, 3, 63, 64, 100, 199, 299]);
+    let mut dst = Bitmap::new(400);
+    dst.copy_range_from(&src, .., 0);
+    assert_eq!(dst.to_indices_true(), src.to_indices_true());
+
+    let mut dst = Bitmap::new(400);
+    dst.set_range(.., true);
+    dst.copy_range_from(&src, 3..101, 10);
+    let mut expected: Vec<usize> = (0..400).collect();
+    expected.retain(|i| !(10..108).contains(i));
+    expected.extend([10, 70, 71, 107]);
+    expected.sort();
+    assert_eq!(dst.to_indices_true(), expected);
+}
+
+#[test]
+fn bitmap_copy_range_from_unaligned_test() {
+    let mut src = Bitmap::new(1000);
+    for i in 0..1000 {
+        src.set(i, i % 3 == 0 || i % 7 == 1);
+    }
+    for (lower, upper, dst_offset) in [(0, 1000, 0), (1, 999, 0), (5, 700, 37), (64, 128, 1), (63, 300, 200), (10, 11, 999)] {
+        let mut dst = Bitmap::new(1000);
+        dst.copy_range_from(&src, lower..upper, dst_offset);
+        for i in 0..1000 {
+            let expected = if i >= dst_offset && i < dst_offset + (upper - lower) {
+                src.get(lower + i - dst_offset).unwrap()
+            } else {
+                false
+            };
+            assert_eq!(dst.get(i), Some(expected));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn bitmap_copy_range_from_out_of_bounds_test() {
+    let src = Bitmap::new(100);
+    let mut dst = Bitmap::new(100);
+    dst.copy_range_from(&src, 0..50, 51);
+}
+
+#[test]
+#[should_panic(expected = "Upper bound out of bounds")]
+fn bitmap_copy_range_from_offset_overflow_test() {
+    let src = Bitmap::new(100);
+    let mut dst = Bitmap::new(100);
+    dst.copy_range_from(&src, 0..50, usize::MAX - 10);
+}
+
+#[test]
+fn rank_select_small_test() {
+    let bitmap = bitmap_from_indices(10, &[1, 2, 5, 9]);
+    let rank_select = RankSelectBitmap::new(bitmap);
+    assert_eq!(rank_select.count_ones(), 4);
+    assert_eq!(rank_select.count_zeros(), 6);
+    assert_eq!(rank_select.rank1(0), 0);
+    assert_eq!(rank_select.rank1(2), 1);
+    assert_eq!(rank_select.rank1(6), 3);
+    assert_eq!(rank_select.rank1(10), 4);
+    assert_eq!(rank_select.rank0(10), 6);
+    assert_eq!(rank_select.select1(0), Some(1));
+    assert_eq!(rank_select.select1(3), Some(9));
+    assert_eq!(rank_select.select1(4), None);
+    assert_eq!(rank_select.select0(0), Some(0));
+    assert_eq!(rank_select.select0(5), Some(8));
+    assert_eq!(rank_select.select0(6), None);
+}
+
+#[test]
+#[should_panic]
+fn rank_select_out_of_bounds_test() {
+    let rank_select = RankSelectBitmap::new(Bitmap::new(10));
+    rank_select.rank1(11);
+}
+
+#[test]
+fn rank_select_matches_linear_test() {
+    for bit_count in [0, 1, 63, 64, 512, 513, 1000, 4096, 5000] {
+        let mut bitmap = Bitmap::new(bit_count);
+        for i in 0..bit_count {
+            bitmap.set(i, i % 3 == 0 || i % 17 == 5 || (600..900).contains(&i));
+        }
+        let ones = bitmap.to_indices_true();
+        let zeros = bitmap.to_indices_false();
+        let rank_select = RankSelectBitmap::new(bitmap);
+
+        let mut rank = 0;
+        for i in 0..=bit_count {
+            assert_eq!(rank_select.rank1(i), rank);
+            assert_eq!(rank_select.rank0(i), i - rank);
+            if i < bit_count && rank_select.get(i).unwrap() {
+                rank += 1;
+            }
+        }
+        for (rank, index) in ones.iter().enumerate() {
+            assert_eq!(rank_select.select1(rank), Some(*index));
+        }
+        for (rank, index) in zeros.iter().enumerate() {
+            assert_eq!(rank_select.select0(rank), Some(*index));
+        }
+        assert_eq!(rank_select.select1(ones.len()), None);
+        assert_eq!(rank_select.select0(zeros.len()), None);
+    }
+}
+
+#[test]
+fn rank_select_into_inner_test() {
+    let rank_select = RankSelectBitmap::new(bitmap_from_indices(100, &[10]));
+    assert_eq!(rank_select.bit_capacity(), 100);
+    assert_eq!(rank_select.bitmap().first_one(0), Some(10));
+    let mut bitmap = rank_select.into_inner();
+    bitmap.set(20, true);
+    assert_eq!(RankSelectBitmap::new(bitmap).rank1(100), 2);
+}
+
+#[test]
+fn bitmap_batch_beyond_256_words_test() {
+    let mut bitmap = Bitmap::new(100_000);
+    let offsets = [3, 16_384, 16_447, 70_000, 99_999];
+    let handles = Handle::new_batch_checked(&offsets, &bitmap);
+    assert_eq!(handles.capacity(), 4);
+    assert!(!bitmap.check_batch(handles.as_slice()));
+    assert!(!bitmap.check_batch_any(handles.as_slice()));
+
+    bitmap.set_batch(handles.as_slice());
+    assert_eq!(bitmap.to_indices_true(), offsets.to_vec());
+    // Word 0 must not have been touched by the wrapped around chunk of 16384
+    assert_eq!(bitmap.count_ones(0, 64), 1);
+    assert!(bitmap.check_batch(handles.as_slice()));
+
+    bitmap.clear_batch(&handles.as_slice()[..2]);
+    assert_eq!(bitmap.to_indices_true(), vec![70_000, 99_999]);
+    assert!(!bitmap.check_batch(handles.as_slice()));
+    assert!(bitmap.check_batch_any(handles.as_slice()));
+
+    bitmap.toggle_batch(handles.as_slice());
+    assert_eq!(bitmap.to_indices_true(), vec![3, 16_384, 16_447]);
+}
+
+#[test]
+#[should_panic(expected = "Bit index out of bounds")]
+fn bitmap_batch_checked_out_of_bounds_test() {
+    let bitmap = Bitmap::new(100);
+    Handle::new_batch_checked(&[5, 100], &bitmap);
+}
+
+#[test]
+#[should_panic(expected = "Handle out of bounds")]
+fn bitmap_set_batch_out_of_bounds_test() {
+    let mut bitmap = Bitmap::new(100);
+    let handles = Handle::new_batch(&[5, 100]);
+    bitmap.set_batch(handles.as_slice());
+}
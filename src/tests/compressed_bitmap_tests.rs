@@ -0,0 +1,251 @@
+use crate::data_structs::bitmap::compressed::CompressedBitmap;
+use crate::data_structs::bitmap::Bitmap;
+
+fn compressed_from_indices(indices: &[usize]) -> CompressedBitmap {
+    let mut bitmap = CompressedBitmap::new();
+    for index in indices {
+        bitmap.set(*index, true);
+    }
+    bitmap
+}
+
+#[test]
+fn test_compressed_set_get() {
+    let mut bitmap = CompressedBitmap::new();
+    assert!(bitmap.is_empty());
+    bitmap.set(3, true);
+    bitmap.set(70_000, true);
+    bitmap.set(u32::MAX as usize, true);
+    assert_eq!(bitmap.get(3), Some(true));
+    assert_eq!(bitmap.get(70_000), Some(true));
+    assert_eq!(bitmap.get(u32::MAX as usize), Some(true));
+    assert_eq!(bitmap.get(4), Some(false));
+    assert_eq!(bitmap.get(1 << 40), None);
+    assert_eq!(bitmap.len(), 3);
+    assert_eq!(bitmap.container_count(), 3);
+
+    bitmap.set(70_000, false);
+    assert_eq!(bitmap.get(70_000), Some(false));
+    assert_eq!(bitmap.container_count(), 2);
+    assert_eq!(bitmap.to_indices_true(), vec![3, u32::MAX as usize]);
+}
+
+#[test]
+#[should_panic(expected = "Bit index out of bounds")]
+fn test_compressed_set_out_of_universe() {
+    let mut bitmap = CompressedBitmap::new();
+    bitmap.set(1 << 32, true);
+}
+
+#[test]
+fn test_compressed_array_to_bitmap_container() {
+    let mut bitmap = CompressedBitmap::new();
+    for i in 0..10_000 {
+        bitmap.set(i * 2, true);
+    }
+    assert_eq!(bitmap.len(), 10_000);
+    assert_eq!(bitmap.container_count(), 1);
+    for i in 0..10_000 {
+        assert_eq!(bitmap.get(i * 2), Some(true));
+        assert_eq!(bitmap.get(i * 2 + 1), Some(false));
+    }
+    for i in 0..9_000 {
+        bitmap.set(i * 2, false);
+    }
+    assert_eq!(bitmap.len(), 1_000);
+    assert_eq!(bitmap.first_one(0), Some(18_000));
+    assert_eq!(bitmap.iter_ones().count(), 1_000);
+}
+
+#[test]
+fn test_compressed_count_and_first_one() {
+    let bitmap = compressed_from_indices(&[1, 5, 65_535, 65_536, 200_000]);
+    assert_eq!(bitmap.count_ones(0, 1 << 32), 5);
+    assert_eq!(bitmap.count_ones(2, 65_536), 2);
+    assert_eq!(bitmap.count_ones(65_535, 65_537), 2);
+    assert_eq!(bitmap.count_ones(6, 6), 0);
+    assert_eq!(bitmap.first_one(0), Some(1));
+    assert_eq!(bitmap.first_one(6), Some(65_535));
+    assert_eq!(bitmap.first_one(65_537), Some(200_000));
+    assert_eq!(bitmap.first_one(200_001), None);
+    assert_eq!(bitmap.last_one(), Some(200_000));
+}
+
+#[test]
+fn test_compressed_count_and_first_zero() {
+    let mut bitmap = compressed_from_indices(&[0, 1, 2, 5, 65_535, 65_536, 200_000]);
+    assert_eq!(bitmap.count_zeros(0, 1 << 32), (1 << 32) - 7);
+    assert_eq!(bitmap.count_zeros(0, 6), 2);
+    assert_eq!(bitmap.count_zeros(65_535, 65_537), 0);
+    assert_eq!(bitmap.count_zeros(7, 7), 0);
+    assert_eq!(bitmap.first_zero(0), Some(3));
+    assert_eq!(bitmap.first_zero(5), Some(6));
+    assert_eq!(bitmap.first_zero(65_535), Some(65_537));
+    assert_eq!(bitmap.first_zero(70_000), Some(70_000));
+    assert_eq!(bitmap.first_zero(1 << 32), None);
+
+    // Full chunks in every container kind are skipped
+    for i in 131_072..196_608 {
+        bitmap.set(i, true);
+    }
+    assert_eq!(bitmap.first_zero(131_072), Some(196_608));
+    bitmap.optimize();
+    assert_eq!(bitmap.first_zero(131_072), Some(196_608));
+    assert_eq!(bitmap.first_zero(150_000), Some(196_608));
+    let dense = compressed_from_indices(&(0..65_536).filter(|i| i % 3 != 2).collect::<Vec<_>>());
+    assert_eq!(dense.first_zero(3), Some(5));
+    assert_eq!(dense.first_zero(65_534), Some(65_534));
+    assert_eq!(dense.first_zero(65_535), Some(65_536));
+
+    let mut top = CompressedBitmap::new();
+    for i in (1 << 32) - 65_536..1 << 32 {
+        top.set(i, true);
+    }
+    assert_eq!(top.first_zero((1 << 32) - 10), None);
+}
+
+#[test]
+#[should_panic(expected = "Upper bound out of bounds")]
+fn test_compressed_count_zeros_out_of_universe() {
+    CompressedBitmap::new().count_zeros(0, (1 << 32) + 1);
+}
+
+#[test]
+fn test_compressed_iterator_after_exhausted() {
+    let dense = compressed_from_indices(&(0..10_000).map(|i| i * 2).collect::<Vec<_>>());
+    let mut runs = compressed_from_indices(&(0..1_000).chain(70_000..70_100).collect::<Vec<_>>());
+    runs.optimize();
+    let sparse = compressed_from_indices(&[3, 70_000]);
+    for bitmap in [dense, runs, sparse] {
+        let mut iterator = bitmap.iter_ones();
+        assert_eq!(iterator.by_ref().count(), bitmap.len());
+        for _ in 0..3 {
+            assert_eq!(iterator.next(), None);
+        }
+    }
+}
+
+#[test]
+fn test_compressed_optimize_runs() {
+    let mut bitmap = CompressedBitmap::new();
+    for i in 100..60_000 {
+        bitmap.set(i, true);
+    }
+    bitmap.set(62_000, true);
+    let before = bitmap.to_indices_true();
+    bitmap.optimize();
+    assert_eq!(bitmap.to_indices_true(), before);
+    assert_eq!(bitmap.len(), 59_901);
+    assert_eq!(bitmap.count_ones(0, 200), 100);
+    assert_eq!(bitmap.first_one(60_000), Some(62_000));
+    assert_eq!(bitmap.get(59_999), Some(true));
+    assert_eq!(bitmap.get(60_000), Some(false));
+
+    // Mutating a run container keeps its contents
+    bitmap.set(60_000, true);
+    bitmap.set(100, false);
+    assert_eq!(bitmap.len(), 59_901);
+    assert_eq!(bitmap.first_one(0), Some(101));
+    assert_eq!(bitmap.get(60_000), Some(true));
+}
+
+#[test]
+fn test_compressed_run_container_edits() {
+    let mut bitmap = CompressedBitmap::new();
+    let mut expected = Bitmap::new(1 << 16);
+    for (start, end) in [(10, 20), (30, 40), (1_000, 5_000)] {
+        for i in start..=end {
+            bitmap.set(i, true);
+            expected.set(i, true);
+        }
+    }
+    bitmap.optimize();
+    // Grows, merges and adds runs, then splits, trims and drops them
+    let sets = [25, 21, 29, 22, 23, 24, 26, 27, 28, 9, 41, 50].map(|index| (index, true));
+    let clears = [3_000, 1_000, 5_000, 50].map(|index| (index, false));
+    for (index, value) in sets.into_iter().chain(clears) {
+        bitmap.set(index, value);
+        expected.set(index, value);
+        assert_eq!(bitmap.to_indices_true(), expected.to_indices_true());
+        assert_eq!(bitmap.len(), expected.count_ones(0, 1 << 16));
+    }
+    for i in 9..=41 {
+        bitmap.set(i, false);
+    }
+    assert_eq!(bitmap.first_one(0), Some(1_001));
+    assert_eq!(bitmap.count_ones(0, 3_000), 1_999);
+}
+
+#[test]
+fn test_compressed_rank_across_containers() {
+    let indices: Vec<usize> = (0..40).map(|key| (key << 16) * 3 + key * 7).collect();
+    let mut bitmap = compressed_from_indices(&indices);
+    for i in 0..5_000 {
+        bitmap.set((5 << 16) + i, true);
+    }
+    bitmap.optimize();
+    for (position, index) in indices.iter().enumerate() {
+        let extra = if *index > 5 << 16 { 5_000 } else { 0 };
+        assert_eq!(bitmap.count_ones(0, *index), position + extra);
+        assert_eq!(bitmap.count_ones(*index, 1 << 32), indices.len() - position + 5_000 - extra);
+    }
+}
+
+#[test]
+fn test_compressed_set_algebra() {
+    let lhs = compressed_from_indices(&[1, 2, 3, 100_000, 300_000]);
+    let rhs = compressed_from_indices(&[2, 3, 4, 200_000, 300_000]);
+    assert_eq!((&lhs & &rhs).to_indices_true(), vec![2, 3, 300_000]);
+    assert_eq!((&lhs | &rhs).to_indices_true(), vec![1, 2, 3, 4, 100_000, 200_000, 300_000]);
+    assert_eq!((&lhs ^ &rhs).to_indices_true(), vec![1, 4, 100_000, 200_000]);
+    assert_eq!(lhs.and_not(&rhs).to_indices_true(), vec![1, 100_000]);
+
+    let mut assigned = lhs.clone();
+    assigned &= &rhs;
+    assert_eq!(assigned.container_count(), 2);
+    assigned |= &lhs;
+    assert_eq!(assigned.to_indices_true(), lhs.to_indices_true());
+    assigned ^= &lhs;
+    assert!(assigned.is_empty());
+}
+
+#[test]
+fn test_compressed_algebra_dense_containers() {
+    let mut lhs = CompressedBitmap::new();
+    let mut rhs = CompressedBitmap::new();
+    for i in 0..20_000 {
+        lhs.set(i, true);
+        rhs.set(i + 10_000, true);
+    }
+    rhs.optimize();
+    let and = &lhs & &rhs;
+    assert_eq!(and.len(), 10_000);
+    assert_eq!(and.first_one(0), Some(10_000));
+    assert_eq!((&lhs | &rhs).len(), 30_000);
+    assert_eq!((&lhs ^ &rhs).len(), 20_000);
+    assert_eq!(lhs.and_not(&rhs).len(), 10_000);
+}
+
+#[test]
+fn test_compressed_bitmap_roundtrip() {
+    let mut bitmap = Bitmap::new(300_000);
+    for i in (0..300_000).step_by(7) {
+        bitmap.set(i, true);
+    }
+    bitmap.set_range(100_000..150_000, true);
+
+    let compressed = CompressedBitmap::from(&bitmap);
+    assert_eq!(compressed.len(), bitmap.count_ones(0, 300_000));
+    assert_eq!(compressed.to_indices_true(), bitmap.to_indices_true());
+
+    let dense = compressed.to_bitmap(300_000);
+    assert_eq!(dense.bit_capacity(), 300_000);
+    assert_eq!(dense.to_indices_true(), bitmap.to_indices_true());
+}
+
+#[test]
+#[should_panic(expected = "Bit index out of bounds")]
+fn test_compressed_to_bitmap_too_small() {
+    let bitmap = compressed_from_indices(&[10, 1_000]);
+    bitmap.to_bitmap(1_000);
+}
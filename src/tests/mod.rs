@@ -12,3 +12,23 @@ mod queue_tests;
 mod stack_tests;
 #[cfg(test)]
 mod atomic_bitmap;
+#[cfg(test)]
+mod split_buffer_tests;
+#[cfg(test)]
+mod spsc_queue_tests;
+#[cfg(test)]
+mod mpmc_queue_tests;
+#[cfg(test)]
+mod ring_buffer_tests;
+#[cfg(test)]
+mod compressed_bitmap_tests;
+#[cfg(test)]
+mod slot_allocator_tests;
+#[cfg(test)]
+mod bit_query_tests;
+#[cfg(test)]
+mod serialization_tests;
+#[cfg(test)]
+mod view_tests;
+#[cfg(test)]
+mod allocator_tests;
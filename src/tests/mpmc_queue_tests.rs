@@ -0,0 +1,120 @@
+use crate::data_structs::mpmc_queue::MpmcQueue;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+pub fn mpmc_queue_init_test() {
+    let queue = MpmcQueue::<i32>::new_pow2_sized(10);
+    assert_eq!(queue.capacity(), 16);
+    assert_eq!(queue.len(), 0);
+    assert!(queue.is_empty());
+
+    let queue = MpmcQueue::<i32>::new_pow2_sized(1);
+    assert_eq!(queue.capacity(), 2);
+}
+
+#[test]
+pub fn mpmc_queue_push_pop_test() {
+    let queue = MpmcQueue::<i32>::new_pow2_sized(8);
+    for i in 0..8 {
+        assert_eq!(queue.try_push(i), Ok(()));
+        assert_eq!(queue.len() as i32, i + 1);
+    }
+    assert_eq!(queue.try_push(8), Err(8));
+    for i in 0..8 {
+        assert_eq!(queue.try_pop(), Some(i));
+    }
+    assert_eq!(queue.try_pop(), None);
+}
+
+#[test]
+pub fn mpmc_queue_wrap_test() {
+    let queue = MpmcQueue::<i32>::new_pow2_sized(2);
+    for i in 0..100 {
+        queue.push(i);
+        assert_eq!(queue.pop(), i);
+    }
+    queue.push(0);
+    queue.push(1);
+    assert_eq!(queue.try_push(2), Err(2));
+    assert_eq!(queue.pop(), 0);
+    queue.push(2);
+    assert_eq!(queue.pop(), 1);
+    assert_eq!(queue.pop(), 2);
+    assert!(queue.is_empty());
+}
+
+#[test]
+pub fn mpmc_queue_stress_test() {
+    const PRODUCERS: usize = 4;
+    const CONSUMERS: usize = 4;
+    const PER_PRODUCER: usize = 20_000;
+    let queue = Arc::new(MpmcQueue::<usize>::new_pow2_sized(64));
+    let popped = Arc::new(AtomicUsize::new(0));
+    let sum = Arc::new(AtomicUsize::new(0));
+
+    let mut handles = Vec::new();
+    for producer in 0..PRODUCERS {
+        let queue = queue.clone();
+        handles.push(thread::spawn(move || {
+            for i in 0..PER_PRODUCER {
+                queue.push(producer * PER_PRODUCER + i);
+            }
+        }));
+    }
+    for _ in 0..CONSUMERS {
+        let queue = queue.clone();
+        let popped = popped.clone();
+        let sum = sum.clone();
+        handles.push(thread::spawn(move || loop {
+            if popped.load(Ordering::Relaxed) >= PRODUCERS * PER_PRODUCER {
+                break;
+            }
+            match queue.try_pop() {
+                Some(value) => {
+                    sum.fetch_add(value, Ordering::Relaxed);
+                    popped.fetch_add(1, Ordering::Relaxed);
+                }
+                None => thread::yield_now(),
+            }
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let total = PRODUCERS * PER_PRODUCER;
+    assert_eq!(popped.load(Ordering::Relaxed), total);
+    assert_eq!(sum.load(Ordering::Relaxed), total * (total - 1) / 2);
+    assert!(queue.is_empty());
+}
+
+#[test]
+pub fn mpmc_queue_per_producer_order_test() {
+    const PRODUCERS: usize = 3;
+    const PER_PRODUCER: usize = 10_000;
+    let queue = Arc::new(MpmcQueue::<(usize, usize)>::new_pow2_sized(32));
+
+    let mut handles = Vec::new();
+    for producer in 0..PRODUCERS {
+        let queue = queue.clone();
+        handles.push(thread::spawn(move || {
+            for i in 0..PER_PRODUCER {
+                queue.push((producer, i));
+            }
+        }));
+    }
+
+    // A single consumer must see every producer's values in the order they were pushed
+    let mut next = [0; PRODUCERS];
+    for _ in 0..PRODUCERS * PER_PRODUCER {
+        let (producer, value) = queue.pop();
+        assert_eq!(next[producer], value);
+        next[producer] += 1;
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(queue.try_pop(), None);
+}
@@ -277,4 +277,383 @@ pub fn test_alternating_push_pop() {
     assert_eq!(queue.dequeue(), None);
 
     assert_eq!(queue.len(), 0);
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn test_growable_queue_push() {
+    let mut queue = Queue::<i32>::new_pow2_sized_growable(4);
+    assert!(queue.is_growable());
+    for i in 0..100 {
+        queue.push(i);
+        assert_eq!(queue.len() as i32, i + 1);
+    }
+    assert_eq!(queue.capacity(), 128);
+    for i in 0..100 {
+        assert_eq!(queue.dequeue(), Some(i));
+    }
+    assert_eq!(queue.dequeue(), None);
+}
+
+#[test]
+pub fn test_growable_queue_grow_wrapped() {
+    let mut queue = Queue::<i32>::new_pow2_sized_growable(4);
+    for i in 0..4 {
+        queue.push(i);
+    }
+    assert_eq!(queue.dequeue(), Some(0));
+    assert_eq!(queue.dequeue(), Some(1));
+    queue.push(4);
+    queue.push(5);
+    // Full and wrapped, next push has to unwrap the data
+    queue.push(6);
+    assert_eq!(queue.capacity(), 8);
+    queue.push(7);
+    for i in 2..8 {
+        assert_eq!(queue.dequeue(), Some(i));
+    }
+    assert_eq!(queue.dequeue(), None);
+}
+
+#[test]
+#[should_panic]
+pub fn test_non_growable_queue_full() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    assert!(!queue.is_growable());
+    for i in 0..5 {
+        queue.push(i);
+    }
+}
+
+#[test]
+pub fn test_queue_extend_by() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        queue.push(i);
+    }
+    queue.extend_pow2_sized_by(4);
+    assert_eq!(queue.capacity(), 8);
+    queue.extend_pow2_sized_by(0);
+    assert_eq!(queue.capacity(), 8);
+    for i in 4..8 {
+        queue.push(i);
+    }
+    for i in 0..8 {
+        assert_eq!(queue.dequeue(), Some(i));
+    }
+}
+
+#[test]
+pub fn test_queue_push_front() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        queue.push_front(i);
+        assert_eq!(queue.len() as i32, i + 1);
+        assert_eq!(queue.front(), Some(&i));
+    }
+    for i in (0..4).rev() {
+        assert_eq!(queue.dequeue(), Some(i));
+    }
+    assert_eq!(queue.dequeue(), None);
+}
+
+#[test]
+#[should_panic]
+pub fn test_queue_push_front_over_capacity() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..5 {
+        queue.push_front(i);
+    }
+}
+
+#[test]
+pub fn test_queue_pop_back() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        queue.push(i);
+    }
+    for i in (0..4).rev() {
+        assert_eq!(queue.back(), Some(&i));
+        assert_eq!(queue.pop_back(), Some(i));
+    }
+    assert_eq!(queue.pop_back(), None);
+    assert_eq!(queue.back(), None);
+}
+
+#[test]
+pub fn test_queue_back_mut() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    queue.push(1);
+    queue.push(2);
+    *queue.back_mut().unwrap() = 100;
+    assert_eq!(queue.back(), Some(&100));
+    assert_eq!(queue.front(), Some(&1));
+}
+
+#[test]
+pub fn test_queue_push_front_wrap() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    // Front starts at 0 so the first push_front has to wrap to the end of the ring
+    queue.push(2);
+    queue.push(3);
+    queue.push_front(1);
+    queue.push_front(0);
+    assert_eq!(queue.len(), 4);
+    for i in 0..4 {
+        assert_eq!(queue.dequeue(), Some(i));
+    }
+}
+
+#[test]
+pub fn test_queue_pop_back_wrap() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        queue.push(i);
+    }
+    assert_eq!(queue.dequeue(), Some(0));
+    assert_eq!(queue.dequeue(), Some(1));
+    queue.push(4);
+    queue.push(5);
+    // End is now at 2, popping from the back has to walk across the wrap point
+    for i in (2..6).rev() {
+        assert_eq!(queue.pop_back(), Some(i));
+    }
+    assert_eq!(queue.pop_back(), None);
+    queue.push(6);
+    assert_eq!(queue.front(), Some(&6));
+    assert_eq!(queue.back(), Some(&6));
+}
+
+#[test]
+pub fn test_queue_deque_mixed() {
+    let mut queue = Queue::<i32>::new_pow2_sized(8);
+    for i in 0..4 {
+        queue.push(i);
+        queue.push_front(-i - 1);
+    }
+    assert_eq!(queue.len(), 8);
+    for i in (-4..4).rev() {
+        assert_eq!(queue.pop_back(), Some(i));
+    }
+    assert!(queue.is_empty());
+}
+
+#[test]
+pub fn test_growable_queue_push_front() {
+    let mut queue = Queue::<i32>::new_pow2_sized_growable(2);
+    queue.push(0);
+    for i in 1..20 {
+        queue.push_front(i);
+    }
+    assert_eq!(queue.capacity(), 32);
+    for i in (1..20).rev() {
+        assert_eq!(queue.dequeue(), Some(i));
+    }
+    assert_eq!(queue.dequeue(), Some(0));
+}
+
+#[test]
+pub fn test_queue_as_slices() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    assert_eq!(queue.as_slices(), (&[][..], &[][..]));
+    for i in 0..3 {
+        queue.push(i);
+    }
+    assert_eq!(queue.as_slices(), (&[0, 1, 2][..], &[][..]));
+    queue.dequeue();
+    queue.dequeue();
+    queue.push(3);
+    queue.push(4);
+    assert_eq!(queue.as_slices(), (&[2, 3][..], &[4][..]));
+    queue.push(5);
+    assert_eq!(queue.as_slices(), (&[2, 3][..], &[4, 5][..]));
+}
+
+#[test]
+pub fn test_queue_iter_wrapped() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        queue.push(i);
+    }
+    queue.dequeue();
+    queue.dequeue();
+    queue.push(4);
+    queue.push(5);
+    let values: Vec<i32> = queue.iter().copied().collect();
+    assert_eq!(values, vec![2, 3, 4, 5]);
+    let values: Vec<i32> = queue.iter().rev().copied().collect();
+    assert_eq!(values, vec![5, 4, 3, 2]);
+    assert_eq!(queue.len(), 4);
+}
+
+#[test]
+pub fn test_queue_iter_mut() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    queue.push(0);
+    queue.push(1);
+    queue.push_front(-1);
+    for value in queue.iter_mut() {
+        *value *= 10;
+    }
+    for (value, expected) in (&queue).into_iter().zip([-10, 0, 10]) {
+        assert_eq!(*value, expected);
+    }
+}
+
+#[test]
+pub fn test_queue_drain() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        queue.push(i);
+    }
+    queue.dequeue();
+    queue.push(4);
+    let drained: Vec<i32> = queue.drain().collect();
+    assert_eq!(drained, vec![1, 2, 3, 4]);
+    assert!(queue.is_empty());
+
+    for i in 0..4 {
+        queue.push(i);
+    }
+    let mut drain = queue.drain();
+    assert_eq!(drain.len(), 4);
+    assert_eq!(drain.next(), Some(0));
+    assert_eq!(drain.next_back(), Some(3));
+    drop(drain);
+    assert!(queue.is_empty());
+    assert_eq!(queue.dequeue(), None);
+}
+
+#[test]
+pub fn test_queue_clone() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        queue.push(i);
+    }
+    queue.dequeue();
+    queue.push(4);
+    let mut cloned = queue.clone();
+    assert_eq!(cloned.capacity(), 4);
+    assert_eq!(cloned.len(), 4);
+    queue.dequeue();
+    // Cloned queue is not affected by the original
+    assert_eq!(cloned.pop_back(), Some(4));
+    cloned.push_front(0);
+    for i in 0..4 {
+        assert_eq!(cloned.dequeue(), Some(i));
+    }
+    assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+}
+
+#[test]
+pub fn test_queue_debug() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    queue.push(1);
+    queue.push(2);
+    queue.push_front(0);
+    assert_eq!(format!("{:?}", queue), "Queue [len=3, cap=4] [ 0 1 2 ]");
+}
+
+#[test]
+pub fn test_queue_extend_from_iter() {
+    let mut queue: Queue<i32> = (0..10).collect();
+    assert!(queue.is_growable());
+    assert_eq!(queue.len(), 10);
+    queue.extend(10..40);
+    assert_eq!(queue.len(), 40);
+    for i in 0..40 {
+        assert_eq!(queue.dequeue(), Some(i));
+    }
+}
+
+#[test]
+pub fn test_queue_index() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        queue.push(i);
+    }
+    for i in 0..4 {
+        assert_eq!(queue[i], i as i32);
+    }
+    queue.dequeue();
+    queue.dequeue();
+    queue.push(4);
+    queue.push(5);
+    // Logical order is kept across the wrap point
+    for i in 0..4 {
+        assert_eq!(queue[i], i as i32 + 2);
+    }
+    queue[3] = 100;
+    assert_eq!(queue.back(), Some(&100));
+}
+
+#[test]
+pub fn test_queue_get() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    assert_eq!(queue.get(0), None);
+    queue.push(1);
+    queue.push_front(0);
+    assert_eq!(queue.get(0), Some(&0));
+    assert_eq!(queue.get(1), Some(&1));
+    assert_eq!(queue.get(2), None);
+    *queue.get_mut(1).unwrap() = 10;
+    assert_eq!(queue.get_mut(2), None);
+    assert_eq!(unsafe { *queue.index_unchecked(1) }, 10);
+    unsafe { *queue.index_unchecked_mut(0) = -1 };
+    assert_eq!(queue.front(), Some(&-1));
+}
+
+#[test]
+#[should_panic]
+pub fn test_queue_index_out_of_bounds() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    queue.push(0);
+    let _ = queue[1];
+}
+
+#[test]
+pub fn test_queue_sliding_window() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    let mut averages = Vec::new();
+    for sample in 0..10 {
+        if queue.len() == queue.capacity() {
+            queue.dequeue();
+        }
+        queue.push(sample);
+        let sum: i32 = (0..queue.len()).map(|i| queue[i]).sum();
+        averages.push(sum / queue.len() as i32);
+    }
+    assert_eq!(averages, vec![0, 0, 1, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+pub fn test_queue_push_overwrite() {
+    let mut queue = Queue::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        assert_eq!(queue.push_overwrite(i), None);
+    }
+    assert_eq!(queue.push_overwrite(4), Some(0));
+    assert_eq!(queue.push_overwrite(5), Some(1));
+    assert_eq!(queue.len(), 4);
+    assert_eq!(queue.front(), Some(&2));
+    assert_eq!(queue.back(), Some(&5));
+    for i in 2..6 {
+        assert_eq!(queue.dequeue(), Some(i));
+    }
+    assert_eq!(queue.dequeue(), None);
+}
+
+#[test]
+pub fn test_queue_zero_capacity() {
+    let mut queue = Queue::<i32>::new_pow2_sized(0);
+    assert_eq!(queue.capacity(), 1);
+    assert_eq!(Queue::<i32>::try_new_pow2_sized(0).unwrap().capacity(), queue.capacity());
+    queue.push(1);
+    assert_eq!(queue.dequeue(), Some(1));
+
+    let mut queue = Queue::<i32>::new_pow2_sized_growable(0);
+    for i in 0..5 {
+        queue.push(i);
+    }
+    assert_eq!(queue.capacity(), 8);
+    assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+}
@@ -0,0 +1,54 @@
+use crate::data_structs::ring_buffer::RingBuffer;
+
+#[test]
+pub fn ring_buffer_init_test() {
+    let ring = RingBuffer::<i32>::new_pow2_sized(10);
+    assert_eq!(ring.capacity(), 16);
+    assert_eq!(ring.len(), 0);
+    assert_eq!(ring.latest(), None);
+    assert_eq!(ring.oldest(), None);
+}
+
+#[test]
+pub fn ring_buffer_push_overwrite_test() {
+    let mut ring = RingBuffer::<i32>::new_pow2_sized(4);
+    for i in 0..4 {
+        assert_eq!(ring.push_overwrite(i), None);
+    }
+    assert!(ring.is_full());
+    for i in 4..10 {
+        assert_eq!(ring.push_overwrite(i), Some(i - 4));
+        assert_eq!(ring.len(), 4);
+        assert_eq!(ring.latest(), Some(&i));
+        assert_eq!(ring.oldest(), Some(&(i - 3)));
+    }
+}
+
+#[test]
+pub fn ring_buffer_iter_test() {
+    let mut ring = RingBuffer::<i32>::new_pow2_sized(4);
+    for i in 0..7 {
+        ring.push_overwrite(i);
+    }
+    assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    assert_eq!(ring.as_slices(), (&[3][..], &[4, 5, 6][..]));
+    for i in 0..4 {
+        assert_eq!(ring[i], i as i32 + 3);
+    }
+    assert_eq!(ring.get(4), None);
+}
+
+#[test]
+pub fn ring_buffer_pop_oldest_test() {
+    let mut ring = RingBuffer::<i32>::new_pow2_sized(4);
+    for i in 0..6 {
+        ring.push_overwrite(i);
+    }
+    assert_eq!(ring.pop_oldest(), Some(2));
+    assert!(!ring.is_full());
+    assert_eq!(ring.push_overwrite(6), None);
+    assert_eq!(ring.push_overwrite(7), Some(3));
+    assert_eq!(format!("{:?}", ring), "RingBuffer [len=4, cap=4] [ 4 5 6 7 ]");
+    ring.clear();
+    assert!(ring.is_empty());
+}
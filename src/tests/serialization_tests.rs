@@ -0,0 +1,322 @@
+use std::io::{Cursor, ErrorKind};
+
+use crate::data_structs::array::Array;
+use crate::data_structs::bitmap::Bitmap;
+use crate::data_structs::queue::Queue;
+use crate::data_structs::stack::Stack;
+
+const MARK: u32 = 0x0102_0304;
+
+#[test]
+fn test_bitmap_binary_roundtrip() {
+    let mut bitmap = Bitmap::new(1_000);
+    for i in (0..1_000).step_by(3) {
+        bitmap.set(i, true);
+    }
+    let mut bytes = Vec::new();
+    bitmap.write_to(&mut bytes).unwrap();
+    // Tag, byte order mark, bit capacity and 125 bytes of bits padded to 128
+    assert_eq!(bytes.len(), 4 + 4 + 8 + 128);
+    assert_eq!(&bytes[..4], b"ETBM");
+    assert_eq!(&bytes[4..8], &MARK.to_le_bytes());
+    assert_eq!(&bytes[8..16], &1_000u64.to_le_bytes());
+    assert_eq!(bytes[16], 0b0100_1001);
+
+    let read = Bitmap::read_from(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(read.bit_capacity(), 1_000);
+    assert_eq!(read.to_indices_true(), bitmap.to_indices_true());
+}
+
+#[test]
+fn test_bitmap_binary_clears_stray_tail_bits() {
+    let mut bytes = Vec::new();
+    Bitmap::new(4).write_to(&mut bytes).unwrap();
+    bytes[16] = 0xFF;
+    let read = Bitmap::read_from(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(read.to_indices_true(), vec![0, 1, 2, 3]);
+    assert_eq!(read.first_zero(0), None);
+}
+
+#[test]
+fn test_bitmap_binary_errors() {
+    let mut bytes = Vec::new();
+    Bitmap::new(100).write_to(&mut bytes).unwrap();
+    bytes.truncate(bytes.len() - 1);
+    let error = Bitmap::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    bytes[0] = b'X';
+    let error = Bitmap::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_array_binary_roundtrip() {
+    let array = Array::from_slice(&[1u32, 2, 3, u32::MAX]);
+    let mut bytes = Vec::new();
+    array.write_to(&mut bytes).unwrap();
+    // The header is padded to 32 bytes
+    assert_eq!(bytes.len(), 32 + 16);
+    let read = Array::<u32>::read_from(&mut Cursor::new(&bytes)).unwrap();
+    assert_eq!(read.as_slice(), array.as_slice());
+
+    let error = Array::<u64>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_array_binary_pod_arrays() {
+    let array = Array::from_slice(&[[1.5f32, 2.5], [3.5, 4.5]]);
+    let mut bytes = Vec::new();
+    array.write_to(&mut bytes).unwrap();
+    let read = Array::<[f32; 2]>::read_from(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(read.as_slice(), array.as_slice());
+}
+
+#[test]
+fn test_stack_binary_roundtrip() {
+    let mut stack = Stack::new_growable(4, 3);
+    stack.push_slice(&[1i64, -2, 3, -4, 5]);
+    let mut bytes = Vec::new();
+    stack.write_to(&mut bytes).unwrap();
+    let mut read = Stack::<i64>::read_from(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(read.as_slice(), stack.as_slice());
+    assert_eq!(read.capacity(), stack.capacity());
+    assert_eq!(read.growth_factor(), 3);
+    assert_eq!(read.top(), Some(&5));
+    assert_eq!(read.pop(), Some(5));
+    read.push(6);
+    assert_eq!(read.as_slice(), &[1, -2, 3, -4, 6]);
+}
+
+#[test]
+fn test_stack_binary_empty() {
+    let stack = Stack::<u8>::new(8);
+    let mut bytes = Vec::new();
+    stack.write_to(&mut bytes).unwrap();
+    let mut read = Stack::<u8>::read_from(&mut Cursor::new(bytes)).unwrap();
+    assert!(read.is_empty());
+    assert!(!read.is_growable());
+    assert_eq!(read.pop(), None);
+    read.push(1);
+    assert_eq!(read.top(), Some(&1));
+}
+
+#[test]
+fn test_queue_binary_roundtrip_wrapped() {
+    let mut queue = Queue::new_pow2_sized(8);
+    for i in 0..8u16 {
+        queue.push(i);
+    }
+    for _ in 0..5 {
+        queue.dequeue();
+    }
+    queue.push(8);
+    queue.push(9);
+    let mut bytes = Vec::new();
+    queue.write_to(&mut bytes).unwrap();
+
+    let mut read = Queue::<u16>::read_from(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(read.capacity(), 8);
+    assert!(!read.is_growable());
+    assert_eq!(read.iter().copied().collect::<Vec<_>>(), vec![5, 6, 7, 8, 9]);
+    for i in 10..13 {
+        read.push(i);
+    }
+    assert_eq!(read.len(), 8);
+    assert_eq!(read.iter().copied().collect::<Vec<_>>(), (5..13).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_queue_binary_invalid_header() {
+    let queue = Queue::<u8>::new_pow2_sized(4);
+    let mut bytes = Vec::new();
+    queue.write_to(&mut bytes).unwrap();
+    // Capacity that is not a power of two
+    bytes[16..24].copy_from_slice(&3u64.to_le_bytes());
+    let error = Queue::<u8>::read_from(&mut Cursor::new(bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+/// Padded tag, byte order mark and little-endian u64 fields without a payload. Bitmaps mark
+/// their payload as little-endian, the other types as native.
+fn header(tag: &[u8; 4], fields: &[u64]) -> Vec<u8> {
+    let mut bytes = tag.to_vec();
+    let mark = if tag == b"ETBM" { MARK.to_le_bytes() } else { MARK.to_ne_bytes() };
+    bytes.extend_from_slice(&mark);
+    for field in fields {
+        bytes.extend_from_slice(&field.to_le_bytes());
+    }
+    bytes.resize(bytes.len().next_multiple_of(16), 0);
+    bytes
+}
+
+#[test]
+fn test_binary_huge_capacity_is_invalid_data() {
+    let huge = 1u64 << 50;
+    for capacity in [huge, u64::MAX] {
+        let bytes = header(b"ETAR", &[8, capacity]);
+        let error = Array::<u64>::read_from(&mut Cursor::new(bytes)).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let bytes = header(b"ETST", &[8, capacity, 0, 0]);
+        let error = Stack::<u64>::read_from(&mut Cursor::new(bytes)).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+    for bit_capacity in [huge << 6, u64::MAX] {
+        let bytes = header(b"ETBM", &[bit_capacity]);
+        let error = Bitmap::read_from(&mut Cursor::new(bytes)).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+    let bytes = header(b"ETQU", &[8, huge, 0, 0]);
+    let error = Queue::<u64>::read_from(&mut Cursor::new(bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_binary_truncated_header() {
+    let mut bytes = header(b"ETAR", &[4, 2]);
+    bytes.truncate(9);
+    let error = Array::<u32>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    let error = Bitmap::read_from(&mut Cursor::new(&bytes[..2])).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    let bytes = header(b"ETST", &[4, 2]);
+    let error = Stack::<u32>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    let bytes = header(b"ETQU", &[4, 2, 0]);
+    let error = Queue::<u32>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_binary_short_payload_is_invalid_data() {
+    let mut bytes = header(b"ETAR", &[1, 1 << 40]);
+    bytes.extend_from_slice(&[7; 100]);
+    let error = Array::<u8>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let mut bytes = header(b"ETST", &[4, 8, 0, 1 << 40]);
+    bytes.extend_from_slice(&[7; 100]);
+    let error = Stack::<u32>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let mut bytes = header(b"ETQU", &[4, 1 << 40, 0, 1 << 39]);
+    bytes.extend_from_slice(&[7; 100]);
+    let error = Queue::<u32>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_binary_payloads_are_padded() {
+    let mut bytes = Vec::new();
+    Array::from_slice(&[1u8, 2, 3]).write_to(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 32 + 16);
+    let mut queue = Queue::<u16>::new_pow2_sized(4);
+    queue.extend([1, 2, 3]);
+    queue.write_to(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 48 + 48 + 16);
+    Bitmap::new(3).write_to(&mut bytes).unwrap();
+    assert_eq!(bytes.len() % 16, 0);
+
+    // Streams written back to back read back in order
+    let mut cursor = Cursor::new(&bytes);
+    assert_eq!(Array::<u8>::read_from(&mut cursor).unwrap().as_slice(), &[1, 2, 3]);
+    assert_eq!(Queue::<u16>::read_from(&mut cursor).unwrap().len(), 3);
+    assert_eq!(Bitmap::read_from(&mut cursor).unwrap().bit_capacity(), 3);
+}
+
+#[test]
+fn test_binary_byte_order_mismatch() {
+    let mut bytes = Vec::new();
+    Array::from_slice(&[1u32, 2]).write_to(&mut bytes).unwrap();
+    bytes[4..8].reverse();
+    let error = Array::<u32>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let mut bytes = Vec::new();
+    Stack::<u32>::new(2).write_to(&mut bytes).unwrap();
+    bytes[4..8].reverse();
+    let error = Stack::<u32>::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let mut bytes = Vec::new();
+    Bitmap::new(10).write_to(&mut bytes).unwrap();
+    bytes[4..8].reverse();
+    let error = Bitmap::read_from(&mut Cursor::new(&bytes)).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "serde")]
+mod serde_tests {
+    use crate::data_structs::array::Array;
+    use crate::data_structs::bitmap::Bitmap;
+    use crate::data_structs::queue::Queue;
+    use crate::data_structs::stack::Stack;
+
+    #[test]
+    fn test_bitmap_serde_roundtrip() {
+        let mut bitmap = Bitmap::new(20);
+        bitmap.set(0, true);
+        bitmap.set(9, true);
+        bitmap.set(19, true);
+        let json = serde_json::to_string(&bitmap).unwrap();
+        assert_eq!(json, r#"{"bit_capacity":20,"bytes":[1,2,8]}"#);
+        let read: Bitmap = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.to_indices_true(), vec![0, 9, 19]);
+
+        assert!(serde_json::from_str::<Bitmap>(r#"{"bit_capacity":20,"bytes":[1,2]}"#).is_err());
+    }
+
+    #[test]
+    fn test_array_serde_roundtrip() {
+        let array = Array::from_slice(&[3u8, 1, 2]);
+        let json = serde_json::to_string(&array).unwrap();
+        assert_eq!(json, "[3,1,2]");
+        let read: Array<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.as_slice(), &[3, 1, 2]);
+    }
+
+    #[test]
+    fn test_stack_serde_roundtrip() {
+        let mut stack = Stack::new(4);
+        stack.push_slice(&[1, 2, 3]);
+        let json = serde_json::to_string(&stack).unwrap();
+        assert_eq!(json, r#"{"capacity":4,"growth_factor":0,"items":[1,2,3]}"#);
+        let read: Stack<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.as_slice(), &[1, 2, 3]);
+        assert_eq!(read.capacity(), 4);
+
+        assert!(serde_json::from_str::<Stack<i32>>(r#"{"capacity":1,"growth_factor":0,"items":[1,2]}"#).is_err());
+    }
+
+    #[test]
+    fn test_queue_serde_roundtrip() {
+        let mut queue = Queue::new_pow2_sized_growable(2);
+        queue.push(1);
+        queue.push(2);
+        queue.dequeue();
+        queue.push(3);
+        let json = serde_json::to_string(&queue).unwrap();
+        assert_eq!(json, r#"{"capacity":2,"growable":true,"items":[2,3]}"#);
+        let mut read: Queue<u32> = serde_json::from_str(&json).unwrap();
+        assert!(read.is_growable());
+        read.push(4);
+        assert_eq!(read.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    fn capacity_error<T: serde::de::DeserializeOwned>(json: &str) -> String {
+        serde_json::from_str::<T>(json).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_serde_huge_capacity_is_error() {
+        let huge = 1u64 << 50;
+        let json = format!(r#"{{"capacity":{},"growth_factor":0,"items":[]}}"#, huge);
+        assert!(capacity_error::<Stack<u64>>(&json).contains("Capacity too large"));
+        let json = format!(r#"{{"capacity":{},"growth_factor":2,"items":[1]}}"#, huge);
+        assert!(capacity_error::<Stack<u64>>(&json).contains("Capacity too large"));
+        let json = format!(r#"{{"capacity":{},"growable":true,"items":[]}}"#, huge);
+        assert!(capacity_error::<Queue<u64>>(&json).contains("Capacity too large"));
+    }
+}
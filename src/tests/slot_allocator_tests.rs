@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread;
+
+use crate::data_structs::bitmap::atomic_bitmap::Mode;
+use crate::data_structs::bitmap::slot_allocator::AtomicSlotAllocator;
+
+#[test]
+fn test_slot_allocator_acquire_all() {
+    let allocator = AtomicSlotAllocator::new(100);
+    let mut slots = HashSet::new();
+    for _ in 0..100 {
+        let slot = allocator.acquire().unwrap();
+        assert!(slot < 100);
+        assert!(slots.insert(slot));
+    }
+    assert_eq!(allocator.acquire(), None);
+
+    allocator.release(42);
+    assert!(!allocator.is_acquired(42));
+    assert_eq!(allocator.acquire(), Some(42));
+    assert!(allocator.is_acquired(42));
+}
+
+#[test]
+#[should_panic(expected = "Slot was not acquired")]
+fn test_slot_allocator_double_release() {
+    let allocator = AtomicSlotAllocator::new(10);
+    let slot = allocator.acquire().unwrap();
+    allocator.release(slot);
+    allocator.release(slot);
+}
+
+#[test]
+fn test_slot_allocator_contiguous() {
+    let allocator = AtomicSlotAllocator::new(200);
+    assert_eq!(allocator.acquire_n_contiguous(10), Some(0));
+    assert_eq!(allocator.acquire_n_contiguous(100), Some(10));
+    assert_eq!(allocator.acquire_n_contiguous(100), None);
+    assert_eq!(allocator.acquire_n_contiguous(90), Some(110));
+    assert_eq!(allocator.acquire(), None);
+
+    allocator.release_n_contiguous(10, 100);
+    allocator.release(5);
+    // Slot 5 alone is too small, the released range is first fit
+    assert_eq!(allocator.acquire_n_contiguous(3), Some(10));
+    assert_eq!(allocator.acquire_n_contiguous(97), Some(13));
+    assert_eq!(allocator.acquire_n_contiguous(1), Some(5));
+    assert_eq!(allocator.acquire_n_contiguous(1), None);
+}
+
+#[test]
+fn test_slot_allocator_contiguous_skips_taken_slots() {
+    let allocator = AtomicSlotAllocator::new(130);
+    for _ in 0..130 {
+        allocator.acquire().unwrap();
+    }
+    allocator.release_n_contiguous(60, 10);
+    allocator.release_n_contiguous(100, 30);
+    assert_eq!(allocator.acquire_n_contiguous(20), Some(100));
+    assert_eq!(allocator.acquire_n_contiguous(10), Some(60));
+    assert_eq!(allocator.acquire_n_contiguous(11), None);
+    assert_eq!(allocator.acquire_n_contiguous(10), Some(120));
+}
+
+#[test]
+fn test_slot_allocator_release_range_checks_before_clearing() {
+    let allocator = AtomicSlotAllocator::new(130);
+    assert_eq!(allocator.acquire_n_contiguous(70), Some(0));
+    allocator.release(65);
+    let result = catch_unwind(AssertUnwindSafe(|| allocator.release_n_contiguous(0, 70)));
+    assert!(result.is_err());
+    assert_eq!(allocator.bitmap().count_ones(0, 130, Mode::Strict), 69);
+    assert!(allocator.is_acquired(0));
+    assert!(allocator.is_acquired(69));
+}
+
+#[test]
+fn test_slot_allocator_threads_start_apart() {
+    const THREADS: usize = 4;
+    let allocator = Arc::new(AtomicSlotAllocator::new(1 << 16));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let allocator = allocator.clone();
+            thread::spawn(move || allocator.acquire().unwrap() / usize::BITS as usize)
+        })
+        .collect();
+    let words: HashSet<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert_eq!(words.len(), THREADS);
+}
+
+#[test]
+fn test_slot_allocator_concurrent_unique() {
+    const THREADS: usize = 4;
+    const SLOTS: usize = 1_000;
+    let allocator = Arc::new(AtomicSlotAllocator::new(SLOTS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let allocator = allocator.clone();
+            thread::spawn(move || {
+                let mut slots = Vec::new();
+                while let Some(slot) = allocator.acquire() {
+                    slots.push(slot);
+                    thread::yield_now();
+                }
+                slots
+            })
+        })
+        .collect();
+    let mut all = HashSet::new();
+    for handle in handles {
+        for slot in handle.join().unwrap() {
+            assert!(all.insert(slot));
+        }
+    }
+    assert_eq!(all.len(), SLOTS);
+}
+
+#[test]
+fn test_slot_allocator_concurrent_acquire_release() {
+    const THREADS: usize = 4;
+    let allocator = Arc::new(AtomicSlotAllocator::new(64));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let allocator = allocator.clone();
+            thread::spawn(move || {
+                for _ in 0..10_000 {
+                    let slot = allocator.acquire().unwrap();
+                    let range = allocator.acquire_n_contiguous(4);
+                    allocator.release(slot);
+                    if let Some(range) = range {
+                        allocator.release_n_contiguous(range, 4);
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(allocator.acquire_n_contiguous(64), Some(0));
+}
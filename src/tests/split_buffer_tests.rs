@@ -0,0 +1,137 @@
+use crate::data_structs::split_buffer::SplitBuffer;
+
+#[test]
+pub fn split_buffer_init_test() {
+    let buffer = SplitBuffer::<i32>::new(10);
+    assert_eq!(buffer.capacity(), 10);
+    assert_eq!(buffer.len(), 0);
+    assert_eq!(buffer.cursor(), 0);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+pub fn split_buffer_insert_test() {
+    let mut buffer = SplitBuffer::<i32>::new(10);
+    for i in 0..10 {
+        buffer.insert(i);
+        assert_eq!(buffer.len() as i32, i + 1);
+    }
+    assert_eq!(buffer.cursor(), 10);
+    let (left, right) = buffer.as_slices();
+    assert_eq!(left, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert!(right.is_empty());
+}
+
+#[test]
+pub fn split_buffer_insert_grow_test() {
+    let mut buffer = SplitBuffer::<i32>::new(2);
+    for i in 0..5 {
+        buffer.insert(i);
+    }
+    buffer.set_cursor(2);
+    for i in 100..110 {
+        buffer.insert(i);
+    }
+    assert_eq!(buffer.len(), 15);
+    assert!(buffer.capacity() >= 15);
+    let (left, right) = buffer.as_slices();
+    assert_eq!(left, &[0, 1, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109]);
+    assert_eq!(right, &[2, 3, 4]);
+}
+
+#[test]
+pub fn split_buffer_cursor_test() {
+    let mut buffer = SplitBuffer::from_slice(&[0, 1, 2, 3, 4, 5]);
+    assert_eq!(buffer.cursor(), 6);
+    buffer.set_cursor(2);
+    assert_eq!(buffer.cursor(), 2);
+    assert_eq!(buffer.as_slices(), (&[0, 1][..], &[2, 3, 4, 5][..]));
+
+    buffer.set_cursor(5);
+    assert_eq!(buffer.as_slices(), (&[0, 1, 2, 3, 4][..], &[5][..]));
+
+    buffer.set_cursor(0);
+    assert_eq!(buffer.as_slices(), (&[][..], &[0, 1, 2, 3, 4, 5][..]));
+}
+
+#[test]
+pub fn split_buffer_cursor_left_right_test() {
+    let mut buffer = SplitBuffer::from_slice(&[0, 1, 2]);
+    assert!(!buffer.cursor_right());
+    assert!(buffer.cursor_left());
+    assert!(buffer.cursor_left());
+    assert_eq!(buffer.as_slices(), (&[0][..], &[1, 2][..]));
+    assert!(buffer.cursor_left());
+    assert!(!buffer.cursor_left());
+    assert!(buffer.cursor_right());
+    assert_eq!(buffer.as_slices(), (&[0][..], &[1, 2][..]));
+}
+
+#[test]
+#[should_panic]
+pub fn split_buffer_cursor_out_of_bounds_test() {
+    let mut buffer = SplitBuffer::from_slice(&[0, 1, 2]);
+    buffer.set_cursor(4);
+}
+
+#[test]
+pub fn split_buffer_delete_backspace_test() {
+    let mut buffer = SplitBuffer::from_slice(&[0, 1, 2, 3, 4]);
+    buffer.set_cursor(2);
+    assert_eq!(buffer.delete(), Some(2));
+    assert_eq!(buffer.backspace(), Some(1));
+    assert_eq!(buffer.to_vec(), vec![0, 3, 4]);
+    assert_eq!(buffer.cursor(), 1);
+
+    assert_eq!(buffer.backspace(), Some(0));
+    assert_eq!(buffer.backspace(), None);
+    assert_eq!(buffer.delete(), Some(3));
+    assert_eq!(buffer.delete(), Some(4));
+    assert_eq!(buffer.delete(), None);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+pub fn split_buffer_insert_slice_test() {
+    let mut buffer = SplitBuffer::from_slice(&[0, 1, 5, 6]);
+    buffer.set_cursor(2);
+    buffer.insert_slice(&[2, 3, 4]);
+    assert_eq!(buffer.cursor(), 5);
+    assert_eq!(buffer.to_vec(), vec![0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+pub fn split_buffer_index_test() {
+    let mut buffer = SplitBuffer::from_slice(&[0, 1, 2, 3, 4, 5]);
+    buffer.set_cursor(3);
+    for i in 0..6 {
+        assert_eq!(buffer[i], i as i32);
+    }
+    buffer[4] = 100;
+    assert_eq!(buffer.get(4), Some(&100));
+    assert_eq!(buffer.get(6), None);
+}
+
+#[test]
+#[should_panic]
+pub fn split_buffer_index_out_of_bounds_test() {
+    let buffer = SplitBuffer::from_slice(&[0, 1, 2]);
+    let _ = buffer[3];
+}
+
+#[test]
+pub fn split_buffer_extend_test() {
+    let mut buffer = SplitBuffer::from_slice(&[0, 1, 2, 3]);
+    buffer.set_cursor(1);
+    buffer.extend(16);
+    assert_eq!(buffer.capacity(), 16);
+    assert_eq!(buffer.gap_len(), 12);
+    assert_eq!(buffer.as_slices(), (&[0][..], &[1, 2, 3][..]));
+}
+
+#[test]
+pub fn split_buffer_debug_test() {
+    let mut buffer = SplitBuffer::from_slice(&[0, 1, 2]);
+    buffer.set_cursor(1);
+    assert_eq!(format!("{:?}", buffer), "SplitBuffer [len=3, cap=3, cursor=1] [ 0 | 1 2 ]");
+}
@@ -0,0 +1,115 @@
+use crate::data_structs::spsc_queue::SpscQueue;
+use std::thread;
+
+#[test]
+pub fn spsc_queue_init_test() {
+    let queue = SpscQueue::<i32>::new_pow2_sized(10);
+    assert_eq!(queue.capacity(), 16);
+    assert_eq!(queue.len(), 0);
+    assert!(queue.is_empty());
+}
+
+#[test]
+pub fn spsc_queue_push_pop_test() {
+    let (mut producer, mut consumer) = SpscQueue::<i32>::new_pow2_sized(16).split();
+    for i in 0..16 {
+        assert_eq!(producer.try_push(i), Ok(()));
+        assert_eq!(producer.len() as i32, i + 1);
+    }
+    assert!(producer.is_full());
+    assert_eq!(producer.try_push(16), Err(16));
+
+    for i in 0..16 {
+        assert_eq!(consumer.try_pop(), Some(i));
+    }
+    assert_eq!(consumer.try_pop(), None);
+    assert!(consumer.is_empty());
+}
+
+#[test]
+pub fn spsc_queue_wrap_test() {
+    let (mut producer, mut consumer) = SpscQueue::<i32>::new_pow2_sized(4).split();
+    for round in 0..10 {
+        for i in 0..3 {
+            producer.try_push(round * 10 + i).unwrap();
+        }
+        for i in 0..3 {
+            assert_eq!(consumer.try_pop(), Some(round * 10 + i));
+        }
+    }
+    assert!(consumer.is_empty());
+}
+
+#[test]
+pub fn spsc_queue_push_slice_test() {
+    let (mut producer, mut consumer) = SpscQueue::<i32>::new_pow2_sized(8).split();
+    assert_eq!(producer.push_slice(&[0, 1, 2, 3, 4, 5]), 6);
+    let mut out = [0; 4];
+    assert_eq!(consumer.pop_into(&mut out), 4);
+    assert_eq!(out, [0, 1, 2, 3]);
+
+    // Wraps around the end of the ring and only 6 slots are free
+    assert_eq!(producer.push_slice(&[6, 7, 8, 9, 10, 11, 12, 13]), 6);
+    let mut out = [0; 10];
+    assert_eq!(consumer.pop_into(&mut out), 8);
+    assert_eq!(&out[..8], &[4, 5, 6, 7, 8, 9, 10, 11]);
+    assert_eq!(consumer.pop_into(&mut out), 0);
+}
+
+#[test]
+pub fn spsc_queue_threaded_test() {
+    const COUNT: usize = 100_000;
+    let (mut producer, mut consumer) = SpscQueue::<usize>::new_pow2_sized(1024).split();
+    let handle = thread::spawn(move || {
+        for i in 0..COUNT {
+            while producer.try_push(i).is_err() {
+                thread::yield_now();
+            }
+        }
+    });
+
+    let mut expected = 0;
+    while expected < COUNT {
+        match consumer.try_pop() {
+            Some(value) => {
+                assert_eq!(value, expected);
+                expected += 1;
+            }
+            None => thread::yield_now(),
+        }
+    }
+    handle.join().unwrap();
+    assert!(consumer.is_empty());
+}
+
+#[test]
+pub fn spsc_queue_threaded_batch_test() {
+    const COUNT: usize = 100_000;
+    let (mut producer, mut consumer) = SpscQueue::<usize>::new_pow2_sized(256).split();
+    let handle = thread::spawn(move || {
+        let values: Vec<usize> = (0..COUNT).collect();
+        let mut pushed = 0;
+        while pushed < COUNT {
+            let end = (pushed + 100).min(COUNT);
+            let count = producer.push_slice(&values[pushed..end]);
+            if count == 0 {
+                thread::yield_now();
+            }
+            pushed += count;
+        }
+    });
+
+    let mut expected = 0;
+    let mut out = [0; 64];
+    while expected < COUNT {
+        let popped = consumer.pop_into(&mut out);
+        if popped == 0 {
+            thread::yield_now();
+        }
+        for value in &out[..popped] {
+            assert_eq!(*value, expected);
+            expected += 1;
+        }
+    }
+    handle.join().unwrap();
+}
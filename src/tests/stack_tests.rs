@@ -1,3 +1,4 @@
+use crate::data_structs::raw_alloc::Global;
 use crate::data_structs::stack::Stack;
 
 #[test]
@@ -158,7 +159,7 @@ pub fn stack_index_out_of_bounds_test() {
     for i in 0..10 {
         stack.push(i);
     }
-    stack[-10];
+    let _ = stack[-10];
 }
 #[test]
 pub fn test_stack_pop_empty() {
@@ -217,3 +218,182 @@ pub fn test_stack_multiple_extend_debug() {
     assert_eq!(stack.capacity(), 20);
     println!("{:?}", stack);
 }
+
+#[test]
+pub fn test_growable_stack_push() {
+    let mut stack = Stack::<i32>::new_growable(2, 2);
+    assert!(stack.is_growable());
+    assert_eq!(stack.growth_factor(), 2);
+    for i in 0..100 {
+        stack.push(i);
+        assert_eq!(stack.len() as i32, i + 1);
+        assert_eq!(stack.top(), Some(&i));
+    }
+    assert_eq!(stack.capacity(), 128);
+    for i in (0..100).rev() {
+        assert_eq!(stack.pop(), Some(i));
+    }
+    assert_eq!(stack.pop(), None);
+}
+
+#[test]
+pub fn test_growable_stack_growth_factor() {
+    let mut stack = Stack::<i32>::new_growable(2, 3);
+    for i in 0..7 {
+        stack.push(i);
+    }
+    assert_eq!(stack.capacity(), 18);
+    assert_eq!(stack[0], 6);
+    assert_eq!(stack[-6], 0);
+}
+
+#[test]
+#[should_panic]
+pub fn test_growable_stack_invalid_factor() {
+    Stack::<i32>::new_growable(2, 1);
+}
+
+#[test]
+pub fn test_stack_try_push() {
+    let mut stack = Stack::<i32>::new(2);
+    assert!(!stack.is_growable());
+    assert_eq!(stack.try_push(0), Ok(()));
+    assert_eq!(stack.try_push(1), Ok(()));
+    assert_eq!(stack.try_push(2), Err(2));
+    assert_eq!(stack.len(), 2);
+    assert_eq!(stack.top(), Some(&1));
+
+    let mut stack = Stack::<i32>::new_growable(1, 2);
+    stack.push(0);
+    // try_push does not grow even on a growable stack
+    assert_eq!(stack.try_push(1), Err(1));
+    assert_eq!(stack.capacity(), 1);
+}
+
+#[test]
+pub fn test_stack_push_slice() {
+    let mut stack = Stack::<i32>::new(10);
+    stack.push(0);
+    stack.push_slice(&[1, 2, 3]);
+    assert_eq!(stack.len(), 4);
+    assert_eq!(stack.top(), Some(&3));
+    assert_eq!(stack.as_slice(), &[0, 1, 2, 3]);
+    stack.push_slice(&[]);
+    assert_eq!(stack.len(), 4);
+}
+
+#[test]
+#[should_panic]
+pub fn test_stack_push_slice_over_capacity() {
+    let mut stack = Stack::<i32>::new(3);
+    stack.push_slice(&[0, 1, 2, 3]);
+}
+
+#[test]
+pub fn test_growable_stack_push_slice() {
+    let mut stack = Stack::<i32>::new_growable(2, 2);
+    stack.push_slice(&[0, 1, 2]);
+    assert_eq!(stack.capacity(), 4);
+    stack.push_slice(&[3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(stack.capacity(), 10);
+    assert_eq!(stack.as_slice(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+pub fn test_stack_pop_n() {
+    let mut stack = Stack::<i32>::new(10);
+    stack.push_slice(&[0, 1, 2, 3, 4]);
+    assert_eq!(stack.pop_n(3), &[2, 3, 4]);
+    assert_eq!(stack.len(), 2);
+    assert_eq!(stack.top(), Some(&1));
+    assert!(stack.pop_n(0).is_empty());
+    assert_eq!(stack.pop_n(2), &[0, 1]);
+    assert_eq!(stack.pop(), None);
+}
+
+#[test]
+#[should_panic]
+pub fn test_stack_pop_n_too_many() {
+    let mut stack = Stack::<i32>::new(10);
+    stack.push_slice(&[0, 1]);
+    stack.pop_n(3);
+}
+
+#[test]
+pub fn test_stack_pop_into() {
+    let mut stack = Stack::<i32>::new(10);
+    stack.push_slice(&[0, 1, 2, 3, 4]);
+    let mut out = [0; 3];
+    assert_eq!(stack.pop_into(&mut out), 3);
+    assert_eq!(out, [2, 3, 4]);
+    assert_eq!(stack.pop_into(&mut out), 2);
+    assert_eq!(&out[..2], &[0, 1]);
+    assert_eq!(stack.pop_into(&mut out), 0);
+}
+
+#[test]
+pub fn test_stack_iter() {
+    let mut stack = Stack::<i32>::new(10);
+    stack.push_slice(&[0, 1, 2, 3]);
+    assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1, 0]);
+    for value in stack.iter_mut() {
+        *value *= 10;
+    }
+    assert_eq!(stack.as_slice(), &[0, 10, 20, 30]);
+    stack.as_mut_slice()[0] = -1;
+    assert_eq!(stack[-3], -1);
+}
+
+#[test]
+pub fn test_stack_truncate_clear() {
+    let mut stack = Stack::<i32>::new(10);
+    stack.push_slice(&[0, 1, 2, 3]);
+    stack.truncate(10);
+    assert_eq!(stack.len(), 4);
+    stack.truncate(2);
+    assert_eq!(stack.len(), 2);
+    assert_eq!(stack.top(), Some(&1));
+    stack.push(5);
+    assert_eq!(stack.as_slice(), &[0, 1, 5]);
+    stack.clear();
+    assert!(stack.is_empty());
+    assert_eq!(stack.top(), None);
+    stack.push(7);
+    assert_eq!(stack.pop(), Some(7));
+}
+
+#[test]
+pub fn test_stack_extend_from_iter() {
+    let mut stack: Stack<i32> = (0..10).collect();
+    assert!(stack.is_growable());
+    Extend::extend(&mut stack, 10..30);
+    assert_eq!(stack.len(), 30);
+    for i in (0..30).rev() {
+        assert_eq!(stack.pop(), Some(i));
+    }
+}
+
+#[test]
+pub fn test_stack_try_new_growable() {
+    assert!(Stack::<i32>::try_new_growable(4, 0).is_none());
+    assert!(Stack::<i32>::try_new_growable(4, 1).is_none());
+    let stack = Stack::<i32>::try_new_growable_in(4, 3, Global).unwrap();
+    assert_eq!(stack.capacity(), 4);
+    assert_eq!(stack.growth_factor(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Stack capacity overflow")]
+pub fn test_stack_grow_overflow() {
+    let mut stack = Stack::<u8>::new_growable(2, usize::MAX);
+    for i in 0..3 {
+        stack.push(i);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Stack capacity overflow")]
+pub fn test_stack_push_slice_overflow() {
+    let mut stack = Stack::<u8>::new_growable(2, usize::MAX);
+    stack.push_slice(&[0, 1, 2]);
+}
@@ -0,0 +1,207 @@
+use crate::data_structs::array::view::{ArrayView, ArrayViewMut};
+use crate::data_structs::array::Array;
+use crate::data_structs::binary::{as_bytes, as_bytes_mut, ViewError};
+use crate::data_structs::bitmap::view::{BitmapView, BitmapViewMut};
+use crate::data_structs::bitmap::Bitmap;
+
+#[test]
+fn test_array_view() {
+    let words = [1u64, 2, 3, 4];
+    let view = ArrayView::<u32>::from_bytes(as_bytes(&words)).unwrap();
+    assert_eq!(view.capacity(), 8);
+    if cfg!(target_endian = "little") {
+        assert_eq!(view[0], 1);
+        assert_eq!(view[2], 2);
+    }
+    assert_eq!(view[1], 0);
+    assert_eq!(view.get(8), None);
+    assert_eq!(view.iter().copied().filter(|value| *value != 0).count(), 4);
+
+    let view = ArrayView::<u64>::from_bytes(as_bytes(&words)).unwrap();
+    assert_eq!(view.as_slice(), &words);
+}
+
+#[test]
+fn test_array_view_checks() {
+    let words = [0u64; 4];
+    let bytes = as_bytes(&words);
+    assert_eq!(ArrayView::<u64>::from_bytes(&bytes[1..9]).err(), Some(ViewError::Misaligned));
+    assert_eq!(ArrayView::<u64>::from_bytes(&bytes[..12]).err(), Some(ViewError::InvalidLength));
+    assert_eq!(ArrayView::<u32>::from_bytes(&bytes[..12]).unwrap().capacity(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Index out of bounds")]
+fn test_array_view_index_out_of_bounds() {
+    let words = [0u32; 2];
+    let view = ArrayView::<u32>::from_bytes(as_bytes(&words)).unwrap();
+    let _ = view[2];
+}
+
+#[test]
+fn test_array_view_mut() {
+    let mut words = [0u32; 6];
+    {
+        let mut view = ArrayViewMut::<u32>::from_bytes(as_bytes_mut(&mut words)).unwrap();
+        view[1] = 10;
+        *view.get_mut(5).unwrap() = 50;
+        for value in view.iter_mut().take(1) {
+            *value = 7;
+        }
+        assert_eq!(view.as_view().iter().sum::<u32>(), 67);
+    }
+    assert_eq!(words, [7, 10, 0, 0, 0, 50]);
+}
+
+#[test]
+fn test_bitmap_view_queries() {
+    let words = [0b1010usize, 0, 1 << 3, usize::MAX];
+    let bit_capacity = usize::BITS as usize * 2 + 10;
+    let view = BitmapView::from_bytes(as_bytes(&words), bit_capacity).unwrap();
+    // Only 3 of the 4 words are viewed
+    assert_eq!(view.capacity(), 3);
+    let third = usize::BITS as usize * 2 + 3;
+    assert_eq!(view.to_indices_true(), vec![1, 3, third]);
+    assert_eq!(view.count_ones(0, bit_capacity), 3);
+    assert_eq!(view.count_zeros(0, bit_capacity), bit_capacity - 3);
+    assert_eq!(view.count_ones(2, third), 1);
+    assert_eq!(view.first_one(0), Some(1));
+    assert_eq!(view.first_one(4), Some(third));
+    assert_eq!(view.first_one(third + 1), None);
+    assert_eq!(view.first_one_bounds(4, third), None);
+    assert_eq!(view.first_zero(0), Some(0));
+    assert_eq!(view.first_zero_bounds(1, 2), None);
+    assert_eq!(view.get(3), Some(true));
+    assert_eq!(view.get(bit_capacity), None);
+    assert_eq!(view.iter_zeros().count(), bit_capacity - 3);
+}
+
+#[test]
+fn test_bitmap_view_ignores_stray_tail_bits() {
+    let words = [usize::MAX];
+    let view = BitmapView::from_bytes(as_bytes(&words), 5).unwrap();
+    assert_eq!(view.count_ones(0, 5), 5);
+    assert_eq!(view.first_zero(0), None);
+    assert_eq!(view.to_indices_true(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(view.iter_zeros().count(), 0);
+}
+
+#[test]
+fn test_bitmap_view_checks() {
+    let words = [0usize; 2];
+    let bytes = as_bytes(&words);
+    let word_bits = usize::BITS as usize;
+    assert_eq!(
+        BitmapView::from_bytes(bytes, word_bits * 2 + 1).err(),
+        Some(ViewError::InvalidLength)
+    );
+    assert_eq!(BitmapView::from_bytes(&bytes[1..], 8).err(), Some(ViewError::Misaligned));
+    assert_eq!(BitmapView::from_bytes(bytes, word_bits * 2).unwrap().capacity(), 2);
+}
+
+#[test]
+fn test_bitmap_view_mut() {
+    let mut words = [0usize; 3];
+    let bit_capacity = usize::BITS as usize * 3;
+    {
+        let mut view = BitmapViewMut::from_bytes(as_bytes_mut(&mut words), bit_capacity).unwrap();
+        view.set(0, true);
+        view.flip(100);
+        view.set_range(150..160, true);
+        view.set(155, false);
+        assert_eq!(view.count_ones(0, bit_capacity), 11);
+        assert_eq!(view.as_view().first_one(1), Some(100));
+    }
+    let view = BitmapView::from_bytes(as_bytes(&words), bit_capacity).unwrap();
+    assert_eq!(view.first_one(101), Some(150));
+    assert_eq!(view.get(155), Some(false));
+}
+
+#[test]
+#[should_panic(expected = "Bit index out of bounds")]
+fn test_bitmap_view_mut_set_out_of_bounds() {
+    let mut words = [0usize; 1];
+    let mut view = BitmapViewMut::from_bytes(as_bytes_mut(&mut words), 10).unwrap();
+    view.set(10, true);
+}
+
+#[cfg(target_endian = "little")]
+#[test]
+fn test_bitmap_view_matches_bitmap() {
+    let mut bitmap = Bitmap::new(1_000);
+    let mut words = vec![0usize; 1_000usize.div_ceil(usize::BITS as usize)];
+    let mut view = BitmapViewMut::from_bytes(as_bytes_mut(&mut words), 1_000).unwrap();
+    for i in (0..1_000).step_by(7) {
+        bitmap.set(i, true);
+        view.set(i, true);
+    }
+    let view = BitmapView::from_bytes(as_bytes(&words), 1_000).unwrap();
+    assert_eq!(view.to_indices_true(), bitmap.to_indices_true());
+    assert_eq!(view.count_ones(0, 1_000), bitmap.count_ones(0, 1_000));
+    assert_eq!(view.first_zero_bounds(0, 1_000), bitmap.first_zero_bounds(0, 1_000));
+}
+
+#[test]
+fn test_array_view_from_written() {
+    let array = Array::from_slice(&[1u64, 2, 3, u64::MAX]);
+    // Written straight into an aligned buffer, like a memory mapped file
+    let mut buffer = [0u128; 4];
+    array.write_to(&mut as_bytes_mut(&mut buffer)).unwrap();
+    let view = ArrayView::<u64>::from_written(as_bytes(&buffer)).unwrap();
+    assert_eq!(view.as_slice(), array.as_slice());
+
+    assert_eq!(
+        ArrayView::<u32>::from_written(as_bytes(&buffer)).err(),
+        Some(ViewError::InvalidHeader)
+    );
+    assert_eq!(
+        ArrayView::<u64>::from_written(&as_bytes(&buffer)[16..]).err(),
+        Some(ViewError::InvalidHeader)
+    );
+    assert_eq!(
+        ArrayView::<u64>::from_written(&as_bytes(&buffer)[..56]).err(),
+        Some(ViewError::InvalidLength)
+    );
+
+    {
+        let mut view = ArrayViewMut::<u64>::from_written(as_bytes_mut(&mut buffer)).unwrap();
+        view[0] = 10;
+    }
+    let read = Array::<u64>::read_from(&mut as_bytes(&buffer)).unwrap();
+    assert_eq!(read.as_slice(), &[10, 2, 3, u64::MAX]);
+
+    as_bytes_mut(&mut buffer)[4..8].reverse();
+    assert_eq!(
+        ArrayView::<u64>::from_written(as_bytes(&buffer)).err(),
+        Some(ViewError::ByteOrderMismatch)
+    );
+}
+
+#[cfg(target_endian = "little")]
+#[test]
+fn test_bitmap_view_from_written() {
+    let mut bitmap = Bitmap::new(1_000);
+    for i in (0..1_000).step_by(7) {
+        bitmap.set(i, true);
+    }
+    // 16 header bytes and 125 bytes of bits padded to 128
+    let mut buffer = [0u128; 9];
+    bitmap.write_to(&mut as_bytes_mut(&mut buffer)).unwrap();
+    let view = BitmapView::from_written(as_bytes(&buffer)).unwrap();
+    assert_eq!(view.bit_capacity(), 1_000);
+    assert_eq!(view.to_indices_true(), bitmap.to_indices_true());
+    assert_eq!(view.count_ones(0, 1_000), bitmap.count_ones(0, 1_000));
+    assert_eq!(
+        BitmapView::from_written(&as_bytes(&buffer)[..100]).err(),
+        Some(ViewError::InvalidLength)
+    );
+
+    {
+        let mut view = BitmapViewMut::from_written(as_bytes_mut(&mut buffer)).unwrap();
+        view.set(1, true);
+        view.set(7, false);
+    }
+    let read = Bitmap::read_from(&mut as_bytes(&buffer)).unwrap();
+    assert_eq!(read.first_one(1), Some(1));
+    assert_eq!(read.first_one(2), Some(14));
+}
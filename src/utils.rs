@@ -1,3 +1,5 @@
+use std::ops::Deref;
+
 #[inline(always)]
 pub fn rotate_inc(val: usize, mask: usize) -> usize {
     (val + 1) & mask
@@ -5,7 +7,7 @@ pub fn rotate_inc(val: usize, mask: usize) -> usize {
 
 #[inline(always)]
 pub fn rotate_dec(val: usize, mask: usize) -> usize {
-    (val - 1) & mask
+    val.wrapping_sub(1) & mask
 }
 
 #[inline(always)]
@@ -21,3 +23,15 @@ pub fn closest_pow2(mut n: usize) -> usize {
     n |= n >> 32;
     n + 1
 }
+
+/// Aligns the value to its own cache line so neighbouring atomics don't false share.
+#[repr(align(64))]
+pub struct CachePadded<T>(pub T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}